@@ -1,9 +1,10 @@
 use crate::{
     runtime::{RuntimePartialEq, Value},
+    span::Span,
     token::{Kind, Token},
 };
 
-use super::{ExprNode, Ident, Stmt, Var};
+use super::{ExprNode, Ident, Range, RangeBound, Stmt, Var};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct If {
@@ -18,15 +19,21 @@ impl If {
     }
 }
 
+/// A match statement, or a match expression when the branch bodies are
+/// expressions (`Match<ExprNode>`)
 #[derive(Clone, Debug, PartialEq)]
-pub struct Match {
+pub struct Match<T = Vec<Stmt>> {
     pub val: ExprNode,
-    pub branches: Vec<MatchBranch>,
-    pub default: Option<MatchBranch>,
+    pub branches: Vec<MatchBranch<T>>,
+    pub default: Option<MatchBranch<T>>,
 }
 
-impl Match {
-    pub fn new(val: ExprNode, branches: Vec<MatchBranch>, default: Option<MatchBranch>) -> Match {
+impl<T> Match<T> {
+    pub fn new(
+        val: ExprNode,
+        branches: Vec<MatchBranch<T>>,
+        default: Option<MatchBranch<T>>,
+    ) -> Match<T> {
         Match {
             val,
             branches,
@@ -35,21 +42,53 @@ impl Match {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct MatchBranch {
+#[derive(Clone, Debug)]
+pub struct MatchBranch<T = Vec<Stmt>> {
     pub pat: Pattern,
-    pub body: Vec<Stmt>,
+    // Checked after the pattern matches, with any binding in scope
+    pub guard: Option<ExprNode>,
+    pub body: T,
     line: usize,
+    span: Span,
 }
 
-impl MatchBranch {
-    pub fn new(pat: Pattern, body: Vec<Stmt>, line: usize) -> Self {
-        Self { pat, body, line }
+impl<T> MatchBranch<T> {
+    pub fn new(pat: Pattern, body: T, line: usize) -> Self {
+        Self::new_with_guard(pat, None, body, line)
+    }
+
+    pub fn new_with_guard(pat: Pattern, guard: Option<ExprNode>, body: T, line: usize) -> Self {
+        Self {
+            pat,
+            guard,
+            body,
+            line,
+            span: Span::default(),
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
     }
 
     pub fn line(&self) -> usize {
         self.line
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Like `ExprNode`, the span doesn't count
+impl<T: PartialEq> PartialEq for MatchBranch<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pat == other.pat
+            && self.guard == other.guard
+            && self.body == other.body
+            && self.line == other.line
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -57,8 +96,12 @@ pub enum Pattern {
     Var(Var),
     String(String),
     Number(f64),
+    Integer(i64),
     Bool(bool),
     Nil,
+    Range(RangeBound<f64>, RangeBound<f64>),
+    // Matches if any of the patterns do
+    Alternatives(Vec<Pattern>),
 }
 
 impl RuntimePartialEq<Value> for Pattern {
@@ -66,8 +109,16 @@ impl RuntimePartialEq<Value> for Pattern {
         match (self, other) {
             (Self::String(l), Value::String(r)) => l == r,
             (Self::Number(l), Value::Number(r)) => l == r,
+            (Self::Integer(l), Value::Integer(r)) => l == r,
+            (Self::Number(l), Value::Integer(r)) => *l == *r as f64,
+            (Self::Integer(l), Value::Number(r)) => *l as f64 == *r,
             (Self::Bool(l), Value::Bool(r)) => l == r,
             (Self::Nil, Value::Nil) => true,
+            (Self::Range(start, end), val) => match val.as_f64() {
+                Some(n) => (start.clone(), end.clone()).satisfied(n),
+                None => false,
+            },
+            (Self::Alternatives(pats), val) => pats.iter().any(|pat| pat.runtime_eq(val)),
             _ => false,
         }
     }
@@ -81,6 +132,7 @@ impl From<Kind> for Option<Pattern> {
     fn from(kind: Kind) -> Self {
         match &kind {
             Kind::Number(n) => Some(Pattern::Number(*n)),
+            Kind::Integer(n) => Some(Pattern::Integer(*n)),
             Kind::String(s) => Some(Pattern::String(s.clone())),
             Kind::True => Some(Pattern::Bool(true)),
             Kind::False => Some(Pattern::Bool(false)),
@@ -98,12 +150,13 @@ impl From<Token> for Option<Pattern> {
     fn from(tok: Token) -> Self {
         match &tok.kind {
             Kind::Number(n) => Some(Pattern::Number(*n)),
+            Kind::Integer(n) => Some(Pattern::Integer(*n)),
             Kind::String(s) => Some(Pattern::String(s.clone())),
             Kind::True => Some(Pattern::Bool(true)),
             Kind::False => Some(Pattern::Bool(false)),
             Kind::BuggerAll => Some(Pattern::Nil),
             Kind::Ident(ident) => Some(Pattern::Var(Var::new(
-                Ident::new(ident.clone(), tok.line()),
+                Ident::new(ident.clone(), tok.line()).with_span(tok.span()),
                 usize::MAX,
             ))),
             _ => None,
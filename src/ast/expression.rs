@@ -1,25 +1,39 @@
-use crate::{runtime::Value, token::Token};
+use crate::{runtime::Value, span::Span, token::Token};
 
 use super::{
     op::{BinaryOp, UnaryOp},
-    LogicalOp, Var,
+    FnDecl, LogicalOp, Match, Var,
 };
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct ExprNode {
     expr: Expr,
     line: usize,
+    span: Span,
 }
 
 impl ExprNode {
     pub fn new(expr: Expr, line: usize) -> ExprNode {
-        Self { expr, line }
+        Self {
+            expr,
+            line,
+            span: Span::default(),
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
     }
 
     pub fn line(&self) -> usize {
         self.line
     }
 
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
     pub fn expr(&self) -> &Expr {
         &self.expr
     }
@@ -33,6 +47,14 @@ impl ExprNode {
     }
 }
 
+/// Spans are left out so trees can be compared without knowing exactly
+/// where everything was written
+impl PartialEq for ExprNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.expr == other.expr && self.line == other.line
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Expr {
     Unary(UnaryOp, Box<ExprNode>),
@@ -43,6 +65,17 @@ pub enum Expr {
     Var(Var),
     Assign(Var, Box<ExprNode>),
     Call(Box<ExprNode>, Token, Vec<ExprNode>),
+    List(Vec<ExprNode>),
+    Map(Vec<(ExprNode, ExprNode)>),
+    Index(Box<ExprNode>, Box<ExprNode>),
+    IndexAssign(Box<ExprNode>, Box<ExprNode>, Box<ExprNode>),
+    // `target[index] op= value`, kept apart from `IndexAssign` so the target
+    // and index are only evaluated once
+    IndexUpdate(Box<ExprNode>, Box<ExprNode>, BinaryOp, Box<ExprNode>),
+    Lambda(FnDecl),
+    Match(Box<Match<ExprNode>>),
+    // The literal and embedded expression parts of an interpolated string
+    Interpolation(Vec<ExprNode>),
 }
 
 impl Expr {
@@ -0,0 +1,60 @@
+use std::{path::PathBuf, rc::Rc};
+
+use crate::span::Span;
+
+use super::{Ident, Stmt};
+
+#[derive(Clone, Debug)]
+pub struct ModuleImport {
+    pub path: String,
+    pub line: usize,
+    pub span: Span,
+    // Filled in by the resolver once the file has been loaded
+    pub module: Option<Rc<Module>>,
+}
+
+impl ModuleImport {
+    pub fn new(path: String, line: usize) -> Self {
+        Self {
+            path,
+            line,
+            span: Span::default(),
+            module: None,
+        }
+    }
+}
+
+/// Like `ExprNode`, the span doesn't count
+impl PartialEq for ModuleImport {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.line == other.line && self.module == other.module
+    }
+}
+
+/// A lexed, parsed and resolved `.aussie` file
+#[derive(Debug, PartialEq)]
+pub struct Module {
+    pub path: PathBuf,
+    pub stmts: Vec<Stmt>,
+    // Top-level functions and constants the importer gets to use
+    pub exports: Vec<Ident>,
+}
+
+impl Module {
+    pub fn new(path: PathBuf, stmts: Vec<Stmt>) -> Self {
+        let exports = stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Stmt::FnDecl(decl) => Some(decl.ident.clone()),
+                Stmt::VarDecl(decl) if decl.immutable => Some(decl.ident.clone()),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            path,
+            stmts,
+            exports,
+        }
+    }
+}
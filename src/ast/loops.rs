@@ -1,9 +1,11 @@
-use super::{ExprNode, Stmt, Var};
+use super::{ExprNode, Ident, Stmt, Var};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ForLoop {
+    pub label: Option<Ident>,
     pub var: Var,
     pub range: (RangeBound<ExprNode>, RangeBound<ExprNode>),
+    pub step: Option<ExprNode>,
     pub body: Vec<Stmt>,
 }
 
@@ -13,19 +15,58 @@ impl ForLoop {
         range: (RangeBound<ExprNode>, RangeBound<ExprNode>),
         body: Vec<Stmt>,
     ) -> Self {
-        Self { var, range, body }
+        Self::new_with_step(var, range, None, body)
+    }
+
+    pub fn new_with_step(
+        var: Var,
+        range: (RangeBound<ExprNode>, RangeBound<ExprNode>),
+        step: Option<ExprNode>,
+        body: Vec<Stmt>,
+    ) -> Self {
+        Self {
+            label: None,
+            var,
+            range,
+            step,
+            body,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForEachLoop {
+    pub label: Option<Ident>,
+    pub var: Var,
+    pub iterable: ExprNode,
+    pub body: Vec<Stmt>,
+}
+
+impl ForEachLoop {
+    pub fn new(var: Var, iterable: ExprNode, body: Vec<Stmt>) -> Self {
+        Self {
+            label: None,
+            var,
+            iterable,
+            body,
+        }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct WhileLoop {
+    pub label: Option<Ident>,
     pub cond: ExprNode,
     pub body: Vec<Stmt>,
 }
 
 impl WhileLoop {
     pub fn new(cond: ExprNode, body: Vec<Stmt>) -> Self {
-        Self { cond, body }
+        Self {
+            label: None,
+            cond,
+            body,
+        }
     }
 }
 
@@ -50,7 +91,7 @@ impl RangeBound<ExprNode> {
         }
     }
 
-    pub fn to_evaluated(&self, val: f64) -> RangeBound<f64> {
+    pub fn to_evaluated<T>(&self, val: T) -> RangeBound<T> {
         match self {
             Self::Inclusive(_) => RangeBound::Inclusive(val),
             Self::Exclusive(_) => RangeBound::Exclusive(val),
@@ -58,8 +99,8 @@ impl RangeBound<ExprNode> {
     }
 }
 
-impl RangeBound<f64> {
-    pub fn value(&self) -> f64 {
+impl<T: Step> RangeBound<T> {
+    pub fn value(&self) -> T {
         match self {
             Self::Inclusive(val) => *val,
             Self::Exclusive(val) => *val,
@@ -67,31 +108,96 @@ impl RangeBound<f64> {
     }
 }
 
+/// A kind of number that a range can be walked over
+pub trait Step: Copy + PartialOrd {
+    const ONE: Self;
+
+    fn magnitude(self) -> Self;
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_sub(self, other: Self) -> Self;
+}
+
+impl Step for f64 {
+    const ONE: Self = 1f64;
+
+    fn magnitude(self) -> Self {
+        self.abs()
+    }
+
+    fn saturating_add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        self - other
+    }
+}
+
+impl Step for i64 {
+    const ONE: Self = 1;
+
+    fn magnitude(self) -> Self {
+        self.saturating_abs()
+    }
+
+    fn saturating_add(self, other: Self) -> Self {
+        i64::saturating_add(self, other)
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        i64::saturating_sub(self, other)
+    }
+}
+
 pub trait Range<T> {
     fn satisfied(&self, i: T) -> bool;
     fn values(&self) -> (T, T);
-    fn iterate(&self, i: &mut T);
+    /// Steps `i` along, returning false if it can't go any further
+    fn iterate(&self, i: &mut T) -> bool;
 }
 
-impl Range<f64> for (RangeBound<f64>, RangeBound<f64>) {
-    fn iterate(&self, val: &mut f64) {
-        if self.0.value() < self.1.value() {
-            *val += 1f64;
+impl<T: Step> Range<T> for (RangeBound<T>, RangeBound<T>) {
+    fn iterate(&self, val: &mut T) -> bool {
+        (self.0.clone(), self.1.clone(), T::ONE).iterate(val)
+    }
+
+    fn satisfied(&self, i: T) -> bool {
+        (self.0.clone(), self.1.clone(), T::ONE).satisfied(i)
+    }
+
+    fn values(&self) -> (T, T) {
+        (self.0.clone(), self.1.clone(), T::ONE).values()
+    }
+}
+
+/// A range walked in steps of the third element. The walk always heads from
+/// the start towards the end, so only the size of the step matters
+impl<T: Step> Range<T> for (RangeBound<T>, RangeBound<T>, T) {
+    fn iterate(&self, val: &mut T) -> bool {
+        let step = self.2.magnitude();
+        let next = if self.0.value() < self.1.value() {
+            val.saturating_add(step)
         } else if self.0.value() > self.1.value() {
-            *val -= 1f64;
-        }
+            val.saturating_sub(step)
+        } else {
+            *val
+        };
+
+        // A number that stops changing has hit the end of what it can hold
+        let moved = next != *val;
+        *val = next;
+        moved
     }
 
-    fn satisfied(&self, i: f64) -> bool {
-        match self {
+    fn satisfied(&self, i: T) -> bool {
+        match (&self.0, &self.1) {
             (RangeBound::Inclusive(start), RangeBound::Inclusive(end)) => {
                 if start < end {
                     i >= *start && i <= *end
                 } else if start > end {
                     i >= *end && i <= *start
                 } else {
-                    let error_margin = f64::EPSILON;
-                    (i - *start).abs() < error_margin
+                    i == *start
                 }
             }
             (RangeBound::Inclusive(start), RangeBound::Exclusive(end)) => {
@@ -100,8 +206,7 @@ impl Range<f64> for (RangeBound<f64>, RangeBound<f64>) {
                 } else if start > end {
                     i < *end && i >= *start
                 } else {
-                    let error_margin = f64::EPSILON;
-                    (i - *start).abs() < error_margin
+                    i == *start
                 }
             }
             (RangeBound::Exclusive(start), RangeBound::Inclusive(end)) => {
@@ -110,8 +215,7 @@ impl Range<f64> for (RangeBound<f64>, RangeBound<f64>) {
                 } else if end < start {
                     i >= *end && i < *start
                 } else {
-                    let error_margin = f64::EPSILON;
-                    (i - *end).abs() < error_margin
+                    i == *end
                 }
             }
             (RangeBound::Exclusive(start), RangeBound::Exclusive(end)) => {
@@ -126,28 +230,29 @@ impl Range<f64> for (RangeBound<f64>, RangeBound<f64>) {
         }
     }
 
-    fn values(&self) -> (f64, f64) {
-        match self {
+    fn values(&self) -> (T, T) {
+        let step = self.2.magnitude();
+        match (&self.0, &self.1) {
             (RangeBound::Inclusive(a), RangeBound::Inclusive(b)) => (*a, *b),
             (RangeBound::Inclusive(a), RangeBound::Exclusive(b)) => {
                 if a < b {
-                    (*a, *b + 1f64)
+                    (*a, b.saturating_add(step))
                 } else {
-                    (*a, *b - 1f64)
+                    (*a, b.saturating_sub(step))
                 }
             }
             (RangeBound::Exclusive(a), RangeBound::Exclusive(b)) => {
                 if a < b {
-                    (*a + 1f64, *b - 1f64)
+                    (a.saturating_add(step), b.saturating_sub(step))
                 } else {
-                    (*a - 1f64, *b + 1f64)
+                    (a.saturating_sub(step), b.saturating_add(step))
                 }
             }
             (RangeBound::Exclusive(a), RangeBound::Inclusive(b)) => {
                 if a < b {
-                    (*a + 1f64, *b)
+                    (a.saturating_add(step), *b)
                 } else {
-                    (*a - 1f64, *b)
+                    (a.saturating_sub(step), *b)
                 }
             }
         }
@@ -1,15 +1,19 @@
 pub use conditional::*;
 pub use expression::*;
 pub use function::*;
+pub use import::*;
 pub use loops::*;
 pub use op::*;
 pub use statement::*;
+pub use try_catch::*;
 pub use var::*;
 
 mod conditional;
 mod expression;
 mod function;
+mod import;
 mod loops;
 mod op;
 mod statement;
+mod try_catch;
 mod var;
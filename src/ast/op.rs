@@ -35,6 +35,13 @@ pub enum BinaryOp {
     Greater,
     GreaterEqual,
     Modulo,
+    Power,
+    FloorDivide,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 impl From<Kind> for Option<BinaryOp> {
@@ -51,6 +58,13 @@ impl From<Kind> for Option<BinaryOp> {
             Kind::RightBoomerang => Some(BinaryOp::Greater),
             Kind::GTE => Some(BinaryOp::GreaterEqual),
             Kind::Modulo => Some(BinaryOp::Modulo),
+            Kind::Power => Some(BinaryOp::Power),
+            Kind::FloorDivide => Some(BinaryOp::FloorDivide),
+            Kind::BitAnd => Some(BinaryOp::BitAnd),
+            Kind::BitOr => Some(BinaryOp::BitOr),
+            Kind::BitXor => Some(BinaryOp::BitXor),
+            Kind::ShiftLeft => Some(BinaryOp::ShiftLeft),
+            Kind::ShiftRight => Some(BinaryOp::ShiftRight),
             _ => None,
         }
     }
@@ -70,6 +84,13 @@ impl Display for BinaryOp {
             Self::Greater => write!(f, ">"),
             Self::GreaterEqual => write!(f, ">="),
             Self::Modulo => write!(f, "%"),
+            Self::Power => write!(f, "**"),
+            Self::FloorDivide => write!(f, "~/"),
+            Self::BitAnd => write!(f, "&"),
+            Self::BitOr => write!(f, "|"),
+            Self::BitXor => write!(f, "^"),
+            Self::ShiftLeft => write!(f, "<<"),
+            Self::ShiftRight => write!(f, ">>"),
         }
     }
 }
@@ -1,21 +1,29 @@
 use crate::token::Token;
 
-use super::{ExprNode, FnDecl, ForLoop, Ident, If, Match, VarDecl, WhileLoop};
+use super::{
+    ExprNode, FnDecl, ForEachLoop, ForLoop, Ident, If, Match, ModuleImport, TryCatch, VarDecl,
+    WhileLoop,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Stmt {
     Expr(ExprNode),
     Block(Vec<Stmt>),
     If(If),
-    Match(Match),
+    Match(Box<Match>),
     VarDecl(VarDecl),
     FnDecl(FnDecl),
     Print(ExprNode),
     For(Box<ForLoop>),
-    Break(Token),
+    ForEach(Box<ForEachLoop>),
+    Break(Token, Option<Ident>),
+    Continue(Token, Option<Ident>),
     While(Box<WhileLoop>),
     Return(Token, Option<ExprNode>),
+    Try(Box<TryCatch>),
+    Throw(Token, ExprNode),
     Import(Ident),
+    ImportModule(Box<ModuleImport>),
     Exit(bool),
 }
 
@@ -39,10 +47,15 @@ impl Stmt {
             Self::FnDecl(_) => "fn decl",
             Self::Print(_) => "print",
             Self::For(_) => "for",
-            Self::Break(_) => "rbreak",
+            Self::ForEach(_) => "for each",
+            Self::Break(_, _) => "rbreak",
+            Self::Continue(_, _) => "continue",
             Self::While(_) => "while",
             Self::Return(_, _) => "return",
+            Self::Try(_) => "try",
+            Self::Throw(_, _) => "throw",
             Self::Import(_) => "import",
+            Self::ImportModule(_) => "import module",
             Self::Exit(_) => "exit",
         }
         .into()
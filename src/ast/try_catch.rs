@@ -0,0 +1,15 @@
+use super::{Ident, Stmt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TryCatch {
+    pub body: Vec<Stmt>,
+    // Holds the thrown value or error inside the handler
+    pub var: Ident,
+    pub handler: Vec<Stmt>,
+}
+
+impl TryCatch {
+    pub fn new(body: Vec<Stmt>, var: Ident, handler: Vec<Stmt>) -> Self {
+        Self { body, var, handler }
+    }
+}
@@ -1,5 +1,7 @@
 use std::{fmt::Display, rc::Rc};
 
+use crate::span::Span;
+
 use super::ExprNode;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -35,6 +37,10 @@ impl Var {
     pub fn line(&self) -> usize {
         self.ident.line
     }
+
+    pub fn span(&self) -> Span {
+        self.ident.span
+    }
 }
 
 impl From<(Ident, usize)> for Var {
@@ -61,10 +67,11 @@ impl From<(&str, usize, usize)> for Var {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Ident {
     pub name: Rc<str>,
     line: usize,
+    span: Span,
 }
 
 impl Ident {
@@ -72,20 +79,34 @@ impl Ident {
         Self {
             name: Rc::from(name),
             line,
+            span: Span::default(),
         }
     }
 
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
     pub fn line(&self) -> usize {
         self.line
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Like `ExprNode`, the span doesn't count
+impl PartialEq for Ident {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.line == other.line
+    }
 }
 
 impl From<(String, usize)> for Ident {
     fn from(tup: (String, usize)) -> Self {
-        Self {
-            name: Rc::from(tup.0),
-            line: tup.1,
-        }
+        Self::new(tup.0, tup.1)
     }
 }
 
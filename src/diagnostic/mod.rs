@@ -0,0 +1,147 @@
+use std::fmt;
+
+use crate::{
+    lexer::LexError, parser::error::ParseError, resolver::ResolveError,
+    runtime::error::RuntimeError, span::Span,
+};
+
+pub mod render;
+pub use render::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Something else in the source that helps explain a diagnostic, like where
+/// a constant was declared
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A problem found in a program by any stage, from lexing through to
+/// running it. Stages hand these back rather than printing them, so it's
+/// up to whoever is running the program how they get shown
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable code for the kind of problem, like `P002`. The letter is the
+    /// stage it came from: `L`exer, `P`arser, `R`esolver or `E` for runtime
+    pub code: &'static str,
+    pub message: String,
+    pub line: Option<usize>,
+    /// Exactly where the problem is, when that's known
+    pub span: Option<Span>,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error<T: Into<String>>(code: &'static str, message: T, line: Option<usize>) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message: message.into(),
+            line,
+            span: None,
+            labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn warning<T: Into<String>>(code: &'static str, message: T, line: Option<usize>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(code, message, line)
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_label<T: Into<String>>(mut self, span: Span, message: T) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note<T: Into<String>>(mut self, note: T) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+}
+
+/// Without a source to show, the line is written in front of the message
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "[line {}] {}", line, self.message)?,
+            None => write!(f, "{}", self.message)?,
+        }
+        for note in &self.notes {
+            write!(f, "\n  = note: {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&LexError> for Diagnostic {
+    fn from(e: &LexError) -> Self {
+        Self::error(e.code(), e.to_string(), Some(e.line()))
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(e: &ParseError) -> Self {
+        let diagnostic = Self::error(e.code(), e.to_string(), e.line());
+        match e.token() {
+            Some(tok) => diagnostic.with_span(tok.span()),
+            None => diagnostic,
+        }
+    }
+}
+
+impl From<&ResolveError> for Diagnostic {
+    fn from(e: &ResolveError) -> Self {
+        Self::error(e.code(), e.to_string(), Some(e.line()))
+    }
+}
+
+impl From<&RuntimeError> for Diagnostic {
+    fn from(e: &RuntimeError) -> Self {
+        Self::error(e.code(), e.to_string(), e.line())
+    }
+}
+
+/// Errors get passed around as `anyhow::Error`, so find out which stage's
+/// error it really is
+impl From<&anyhow::Error> for Diagnostic {
+    fn from(e: &anyhow::Error) -> Self {
+        if let Some(e) = e.downcast_ref::<LexError>() {
+            e.into()
+        } else if let Some(e) = e.downcast_ref::<ParseError>() {
+            e.into()
+        } else if let Some(e) = e.downcast_ref::<ResolveError>() {
+            e.into()
+        } else if let Some(e) = e.downcast_ref::<RuntimeError>() {
+            e.into()
+        } else {
+            Self::error("E000", e.to_string(), None)
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Write;
+
+use crate::span::Span;
+
+use super::{Diagnostic, Severity};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+/// Turns diagnostics into text showing the lines of source they're about,
+/// with the bit that's wrong underlined, e.g.
+///
+/// ```text
+/// error[R011]: x: OI, YA CAN'T REDEFINE THIS!
+///   |
+/// 1 | i fully reckon x = 1;
+///   |                - CONSTANT DECLARED HERE
+/// 2 | x = 2;
+///   | ^
+/// ```
+///
+/// Diagnostics without a span just say which line they're on, since there's
+/// nothing to point at
+pub struct Renderer<'a> {
+    src: &'a str,
+    colour: bool,
+}
+
+// A span to underline, what to say about it, and whether it's the problem
+// itself rather than a label
+struct Snippet<'a> {
+    span: Span,
+    message: &'a str,
+    primary: bool,
+}
+
+impl Snippet<'_> {
+    fn line(&self) -> usize {
+        self.span.start.line
+    }
+}
+
+impl<'a> Renderer<'a> {
+    /// A renderer for diagnostics about `src`, in plain text. For upside down
+    /// programs `src` should be the flipped text, since that's what spans
+    /// point into
+    pub fn new(src: &'a str) -> Self {
+        Self { src, colour: false }
+    }
+
+    /// Whether to colour the output for a terminal
+    pub fn with_colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut out = String::new();
+
+        let primary = diagnostic.span.map(|span| Snippet {
+            span,
+            message: "",
+            primary: true,
+        });
+        let labels = diagnostic.labels.iter().map(|label| Snippet {
+            span: label.span,
+            message: &label.message,
+            primary: false,
+        });
+        let mut snippets: Vec<Snippet> = primary
+            .into_iter()
+            .chain(labels)
+            .filter(|snippet| self.src_line(snippet.line()).is_some())
+            .collect();
+        snippets.sort_by_key(Snippet::line);
+
+        let (severity, colour) = match diagnostic.severity {
+            Severity::Error => ("error", RED),
+            Severity::Warning => ("warning", YELLOW),
+        };
+        // With no source to show, the line is the only clue where it is
+        let message = match diagnostic.line {
+            Some(line) if snippets.is_empty() => format!("[line {}] {}", line, diagnostic.message),
+            _ => diagnostic.message.clone(),
+        };
+        let _ = writeln!(
+            out,
+            "{}: {}",
+            self.paint(colour, &format!("{}[{}]", severity, diagnostic.code)),
+            self.paint(BOLD, &message),
+        );
+
+        let width = snippets
+            .last()
+            .map_or(0, |snippet| snippet.line().to_string().len());
+        let gutter = " ".repeat(width);
+
+        if !snippets.is_empty() {
+            let _ = writeln!(out, "{} {}", gutter, self.paint(BLUE, "|"));
+        }
+        let mut prev_line = None;
+        for snippet in &snippets {
+            let line = snippet.line();
+            let src_line = self.src_line(line).unwrap_or_default();
+            // Labels on the same line share it
+            if prev_line != Some(line) {
+                let number = format!("{:>width$} |", line, width = width);
+                let _ = writeln!(out, "{} {}", self.paint(BLUE, &number), src_line);
+            }
+            prev_line = Some(line);
+
+            let (mark, colour) = if snippet.primary {
+                ('^', RED)
+            } else {
+                ('-', BLUE)
+            };
+            let (padding, underline) = underline(src_line, snippet.span, mark, snippet.message);
+            let _ = writeln!(
+                out,
+                "{} {} {}{}",
+                gutter,
+                self.paint(BLUE, "|"),
+                padding,
+                self.paint(colour, &underline)
+            );
+        }
+
+        for note in &diagnostic.notes {
+            let _ = writeln!(out, "{} {} note: {}", gutter, self.paint(BLUE, "="), note);
+        }
+
+        out.truncate(out.trim_end().len());
+        out
+    }
+
+    // Spans that were never set are on line 0, which doesn't exist
+    fn src_line(&self, line: usize) -> Option<&str> {
+        self.src.lines().nth(line.checked_sub(1)?)
+    }
+
+    fn paint(&self, colour: &str, text: &str) -> String {
+        if self.colour && !text.is_empty() {
+            format!("{}{}{}", colour, text, RESET)
+        } else {
+            text.to_owned()
+        }
+    }
+}
+
+/// The padding and marks to go under `src_line` for `span`. Only the first
+/// line of a span gets underlined, and an empty span still gets a single mark
+fn underline(src_line: &str, span: Span, mark: char, message: &str) -> (String, String) {
+    let start = span.start.col.saturating_sub(1);
+    let len = src_line.chars().count();
+    let end = if span.end.line == span.start.line {
+        span.end.col.saturating_sub(1)
+    } else {
+        len
+    };
+
+    // Keep tabs so the marks line up however wide they're shown
+    let padding = src_line
+        .chars()
+        .chain(std::iter::repeat(' '))
+        .take(start)
+        .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+        .collect();
+    let mut marks = mark.to_string().repeat(end.saturating_sub(start).max(1));
+    if !message.is_empty() {
+        marks.push(' ');
+        marks.push_str(message);
+    }
+    (padding, marks)
+}
@@ -1,9 +1,15 @@
+use std::mem;
+
 use anyhow::Result;
 use thiserror::Error;
 
-use crate::token::{Kind, Token};
+use crate::{
+    diagnostic::Diagnostic,
+    span::{Position, Span},
+    token::{Kind, StringPart, Token},
+};
 
-use super::source::Source;
+use super::source::{self, Source};
 
 pub struct Lexer<T>
 where
@@ -11,50 +17,85 @@ where
 {
     src: T,
     line: usize,
+    // Byte offset and column of the next character
+    offset: usize,
+    col: usize,
+    // Where the token being lexed started
+    token_start: Position,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a, T: Source> Lexer<T> {
     pub fn new(src: T) -> Self {
-        Self { src, line: 1 }
+        Self {
+            src,
+            line: 1,
+            offset: 0,
+            col: 1,
+            token_start: Position::new(0, 1, 1),
+            diagnostics: Vec::new(),
+        }
     }
 
-    pub fn lex(&mut self) -> (Vec<Token>, bool) {
+    pub fn lex(&mut self) -> (Vec<Token>, Vec<Diagnostic>) {
         let mut tokens: Vec<Token> = Vec::new();
-        let mut had_error = false;
 
         let mut last_nah_yeah: Option<Kind> = None;
         let mut nah_yeah_count = 0;
+        // Where the first `NAH` or `YEAH` of a boolean started
+        let mut nah_yeah_span: Option<Span> = None;
 
         loop {
             match self.next_token() {
                 Ok(tok) => {
+                    // A boolean has to finish with a `!`. Once that's been
+                    // reported it's done with, so what follows gets lexed
+                    // as usual
+                    if last_nah_yeah.is_some()
+                        && !matches!(tok.kind(), Kind::Nah | Kind::Yeah | Kind::Bang)
+                    {
+                        self.error(
+                            LexError::Expected("!".into(), tok.kind().literal(), tok.line()),
+                            tok.span(),
+                        );
+                        let span = nah_yeah_span.take().unwrap_or_default();
+                        tokens.push(Token::new(Kind::Error, span.start.line).with_span(span));
+                        last_nah_yeah = None;
+                        nah_yeah_count = 0;
+                    }
+
                     match tok.kind() {
                         Kind::EOF => {
                             tokens.push(tok);
                             break;
                         }
                         Kind::Cheers => {
-                            tokens.push(Token::new(Kind::Cheers, tok.line()));
-                            tokens.push(Token::new(Kind::EOF, tok.line() + 1));
+                            let end = Span::new(tok.span().end, tok.span().end);
+                            let line = tok.line();
+                            tokens.push(tok);
+                            tokens.push(Token::new(Kind::EOF, line + 1).with_span(end));
                             break;
                         }
                         Kind::Nah | Kind::Yeah => {
                             last_nah_yeah = Some(tok.kind());
                             nah_yeah_count += 1;
+                            nah_yeah_span.get_or_insert(tok.span());
                             // Don't fall through
                             continue;
                         }
                         Kind::Bang => {
                             if let Some(k) = last_nah_yeah {
+                                let span = nah_yeah_span.take().unwrap_or_default().to(tok.span());
                                 let tok = match k {
                                     Kind::Yeah => Token::new(Kind::True, tok.line()),
                                     Kind::Nah => Token::new(Kind::False, tok.line()),
                                     _ => panic!("This should not happen"),
-                                };
+                                }
+                                .with_span(span);
 
                                 if nah_yeah_count < 2 {
-                                    had_error = true;
-                                    eprintln!("{}", LexError::TooLittleNahYeahs(tok.line()));
+                                    let span = tok.span();
+                                    self.error(LexError::TooLittleNahYeahs(tok.line()), span);
                                 }
 
                                 last_nah_yeah = None;
@@ -67,46 +108,82 @@ impl<'a, T: Source> Lexer<T> {
                         _ => {}
                     }
 
-                    if last_nah_yeah.is_some() {
-                        had_error = true;
-                        eprintln!(
-                            "{}",
-                            LexError::Expected("!".into(), tok.kind().literal(), tok.line())
-                        );
-                    }
-
                     tokens.push(tok);
                 }
+                // Leave a token where it went wrong, so the parser can skip it
                 Err(e) => {
-                    had_error = true;
-                    eprintln!("{}", e);
+                    let span = Span::new(self.token_start, self.position());
+                    self.error(e, span);
+                    tokens.push(Token::new(Kind::Error, self.line).with_span(span));
                 }
             }
         }
 
-        (tokens, had_error)
+        (tokens, mem::take(&mut self.diagnostics))
+    }
+
+    fn error<E: Into<anyhow::Error>>(&mut self, e: E, span: Span) {
+        self.diagnostics
+            .push(Diagnostic::from(&e.into()).with_span(span));
     }
 
     fn next_token(&mut self) -> Result<Token> {
         self.eat_whitespace();
+        let start = self.position();
+        self.token_start = start;
 
         let ch = match self.next() {
             Some(ch) => ch,
-            None => return Ok(Token::new(Kind::EOF, self.line)),
+            None => return Ok(Token::new(Kind::EOF, self.line).with_span(Span::new(start, start))),
         };
 
         let kind: Kind = match ch {
-            '%' => Kind::Modulo,
+            '%' => {
+                if self.peek_adv('=') {
+                    Kind::ModuloEqual
+                } else {
+                    Kind::Modulo
+                }
+            }
+            // `~//` is a match arm followed by a comment
+            '~' if self.peek_is('/') && !self.peek_n_is(2, '/', true) => {
+                let _ = self.next();
+                Kind::FloorDivide
+            }
             '~' => Kind::Tilde,
             '?' => Kind::QuestionMark,
+            '^' => Kind::BitXor,
             '[' => Kind::LeftBracket,
             ']' => Kind::RightBracket,
+            '{' => Kind::LeftBrace,
+            '}' => Kind::RightBrace,
+            ':' => Kind::Colon,
             '(' => Kind::LeftParen,
             ')' => Kind::RightParen,
             ',' => Kind::Comma,
-            '+' => Kind::Plus,
-            '-' => Kind::Minus,
-            '*' => Kind::Asterisk,
+            '+' => {
+                if self.peek_adv('=') {
+                    Kind::PlusEqual
+                } else {
+                    Kind::Plus
+                }
+            }
+            '-' => {
+                if self.peek_adv('=') {
+                    Kind::MinusEqual
+                } else {
+                    Kind::Minus
+                }
+            }
+            '*' => {
+                if self.peek_adv('*') {
+                    Kind::Power
+                } else if self.peek_adv('=') {
+                    Kind::AsteriskEqual
+                } else {
+                    Kind::Asterisk
+                }
+            }
             ';' => Kind::Semicolon,
 
             '=' => {
@@ -120,6 +197,8 @@ impl<'a, T: Source> Lexer<T> {
                 if self.peek_adv('/') {
                     self.eat_line();
                     return self.next_token();
+                } else if self.peek_adv('=') {
+                    Kind::SlashEqual
                 } else {
                     Kind::Slash
                 }
@@ -134,6 +213,8 @@ impl<'a, T: Source> Lexer<T> {
             '<' => {
                 if self.peek_adv('=') {
                     Kind::LTE
+                } else if self.peek_adv('<') {
+                    Kind::ShiftLeft
                 } else {
                     Kind::LeftBoomerang
                 }
@@ -141,6 +222,8 @@ impl<'a, T: Source> Lexer<T> {
             '>' => {
                 if self.peek_adv('=') {
                     Kind::GTE
+                } else if self.peek_adv('>') {
+                    Kind::ShiftRight
                 } else {
                     Kind::RightBoomerang
                 }
@@ -149,36 +232,33 @@ impl<'a, T: Source> Lexer<T> {
                 if self.peek_adv('&') {
                     Kind::And
                 } else {
-                    return Err(LexError::ExpectedCharacter(
-                        '&',
-                        self.peek().unwrap_or_default(),
-                        self.line,
-                    )
-                    .into());
+                    Kind::BitAnd
                 }
             }
             '|' => {
                 if self.peek_adv('|') {
                     Kind::Or
                 } else {
-                    return Err(LexError::ExpectedCharacter(
-                        '|',
-                        self.peek().unwrap_or_default(),
-                        self.line,
-                    )
-                    .into());
+                    Kind::BitOr
                 }
             }
             c => match c.to_ascii_lowercase() {
                 // Please keep these alphabetical
                 'b' if self.peek_is('a') => self.eat_keyword_or_ident(c, Kind::Bail)?,
                 'b' if self.peek_is('u') => self.eat_keyword_or_ident(c, Kind::BuggerAll)?,
+                'c' if self.peek_is('h') && self.peek_n_is(2, 'u', false) => {
+                    self.eat_keyword_or_ident(c, Kind::ChuckAWobbly)?
+                }
                 'c' => self.eat_keyword_or_ident(c, Kind::Cheers)?,
                 'f' if self.peek_is('u') => self.eat_keyword_or_ident(c, Kind::FuckinPiker)?,
                 'f' if self.peek_is('r') => self.eat_keyword_or_ident(c, Kind::From)?,
+                'g' if self.peek_is('i') && self.peek_n_is(2, 'v', false) => {
+                    self.eat_keyword_or_ident(c, Kind::GiveItABurl)?
+                }
                 'g' if self.peek_is('i') => self.eat_keyword_or_ident(c, Kind::Gimme)?,
                 'g' if self.peek_is('o') => self.eat_keyword_or_ident(c, Kind::GoodOnYa)?,
                 'g' if self.peek_is('\'') => self.eat_keyword_or_ident(c, Kind::GdayMate)?,
+                'h' => self.eat_keyword_or_ident(c, Kind::Hoppin)?,
                 'i' if self.peek_is('m') => self.eat_keyword_or_ident(c, Kind::Import)?,
                 'i' if self.peek_is('\'') => self.eat_keyword_or_ident(c, Kind::IllHaveA)?,
                 'i' if self.peek_is(' ') => self.eat_reckon_or_fully_reckon(c)?,
@@ -193,7 +273,12 @@ impl<'a, T: Source> Lexer<T> {
                     }
                 }
                 'p' => self.eat_keyword_or_ident(c, Kind::PullYaHeadIn)?,
+                's' if self.peek_is('h') => self.eat_keyword_or_ident(c, Kind::ShellBeRight)?,
+                's' if self.peek_is('t') => self.eat_keyword_or_ident(c, Kind::Strewth)?,
                 't' if self.peek_is('o') => self.eat_keyword_or_ident(c, Kind::To)?,
+                't' if self.peek_is('h') && self.peek_n_is(2, 'r', false) => {
+                    self.eat_keyword_or_ident(c, Kind::Through)?
+                }
                 't' if self.peek_is('h') => self.eat_keyword_or_ident(c, Kind::HardYakkaFor)?,
                 'u' => self.eat_keyword_or_ident(c, Kind::Until)?,
                 'w' if self.peek_is('a') => self.eat_keyword_or_ident(c, Kind::Walkabout)?,
@@ -214,46 +299,175 @@ impl<'a, T: Source> Lexer<T> {
             },
         };
 
-        Ok(Token::new(kind, self.line))
+        Ok(Token::new(kind, self.line).with_span(Span::new(start, self.position())))
     }
 
     fn eat_number(&mut self, first: char) -> Result<Kind> {
+        let number = self.eat_number_literal(first);
+        if number.is_err() {
+            // Skip the rest of a bad number so it isn't lexed as more tokens
+            while let Some(c) = self.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+                    break;
+                }
+                let _ = self.next();
+            }
+        }
+        number
+    }
+
+    fn eat_number_literal(&mut self, first: char) -> Result<Kind> {
+        if first == '0' {
+            if self.peek_is('x') {
+                self.next();
+                return self.eat_radix_number(16, "0x");
+            }
+            if self.peek_is('b') {
+                self.next();
+                return self.eat_radix_number(2, "0b");
+            }
+        }
+
         let mut s = String::from(first);
-        let mut has_decimal = false;
+        self.eat_digits(&mut s, 10)?;
+        let mut is_float = false;
 
-        while let Some(peek) = self.peek() {
-            if peek.is_digit(10) {
+        if self.peek_is('.') {
+            is_float = true;
+            s.push('.');
+            let _ = self.next();
+            self.eat_digits(&mut s, 10)?;
+            if self.peek_is('.') {
+                return Err(LexError::ExtraDecimalPoint(self.line).into());
+            }
+        }
+
+        if self.peek_is('e') {
+            is_float = true;
+            s.push('e');
+            let _ = self.next();
+            if let Some(sign @ ('+' | '-')) = self.peek() {
+                s.push(sign);
                 let _ = self.next();
-                s.push(peek);
-            } else if peek == '.' {
-                if has_decimal {
-                    return Err(LexError::InvalidNumber(self.line).into());
+            }
+            let len = s.len();
+            self.eat_digits(&mut s, 10)?;
+            if s.len() == len {
+                return Err(LexError::MissingExponent(self.line).into());
+            }
+        }
+
+        if !is_float {
+            return s
+                .parse::<i64>()
+                .map(Kind::Integer)
+                .map_err(|_| LexError::NumberTooBig(self.line).into());
+        }
+
+        match s.parse::<f64>() {
+            Ok(f) if f.is_finite() => Ok(Kind::Number(f)),
+            _ => Err(LexError::NumberTooBig(self.line).into()),
+        }
+    }
+
+    /// Eats the digits of a `0x` or `0b` number, the prefix has already been eaten
+    fn eat_radix_number(&mut self, radix: u32, prefix: &'static str) -> Result<Kind> {
+        let mut s = String::new();
+        self.eat_digits(&mut s, radix)?;
+
+        if let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                return Err(LexError::InvalidDigit(c, prefix, self.line).into());
+            }
+        }
+        if s.is_empty() {
+            return Err(LexError::MissingDigits(prefix, self.line).into());
+        }
+
+        match i64::from_str_radix(&s, radix) {
+            Ok(n) => Ok(Kind::Integer(n)),
+            Err(_) => Err(LexError::NumberTooBig(self.line).into()),
+        }
+    }
+
+    /// Eats a run of digits onto `s`, which can be split up with single `_`s
+    /// between them (`1_000_000`)
+    fn eat_digits(&mut self, s: &mut String, radix: u32) -> Result<()> {
+        let mut after_digit = s.chars().last().is_some_and(|c| c.is_digit(radix));
+        let mut after_separator = false;
+
+        while let Some(c) = self.peek() {
+            if c.is_digit(radix) {
+                s.push(c);
+                after_digit = true;
+                after_separator = false;
+            } else if c == '_' {
+                if !after_digit {
+                    return Err(LexError::MisplacedSeparator(self.line).into());
                 }
-                has_decimal = true;
-                s.push(peek);
-                let _ = self.next();
+                after_digit = false;
+                after_separator = true;
             } else {
                 break;
             }
+            let _ = self.next();
         }
 
-        if let Ok(f) = s.parse::<f64>() {
-            return Ok(Kind::Number(f));
+        if after_separator {
+            return Err(LexError::MisplacedSeparator(self.line).into());
         }
 
-        Err(LexError::InvalidNumber(self.line).into())
+        Ok(())
     }
 
     fn eat_string(&mut self) -> Result<Kind> {
+        if self.peek_is('"') && self.peek_n_is(2, '"', true) {
+            self.next();
+            self.next();
+            return self.eat_raw_string();
+        }
+
         let mut s = String::new();
+        let mut parts: Vec<StringPart> = Vec::new();
         let mut ended = false;
+        // Keep eating up to the closing quote after a bad escape or
+        // interpolation so the rest of the string isn't lexed as code
+        let mut failed = false;
+
+        loop {
+            let start = self.position();
+            let next = match self.next() {
+                Some(next) => next,
+                None => break,
+            };
 
-        while let Some(next) = self.next() {
             match next {
                 '"' => {
                     ended = true;
                     break;
                 }
+                '$' if self.peek_is('{') => {
+                    self.next();
+                    if !s.is_empty() {
+                        parts.push(StringPart::Literal(mem::take(&mut s)));
+                    }
+                    match self.eat_interpolation() {
+                        Ok(tokens) => parts.push(StringPart::Expr(tokens)),
+                        // Ran out of source, which is the string's problem
+                        Err(_) if self.peek().is_none() => break,
+                        Err(e) => {
+                            self.error(e, Span::new(start, self.position()));
+                            failed = true;
+                        }
+                    }
+                }
+                '\\' => match self.eat_escape() {
+                    Ok(ch) => s.push(ch),
+                    Err(e) => {
+                        self.error(e, Span::new(start, self.position()));
+                        failed = true;
+                    }
+                },
                 ch => {
                     if ch == '\n' {
                         self.line += 1;
@@ -266,8 +480,149 @@ impl<'a, T: Source> Lexer<T> {
         if !ended {
             return Err(LexError::UnterminatedString(self.line).into());
         }
+        if failed {
+            return Ok(Kind::Error);
+        }
 
-        Ok(Kind::String(s))
+        if parts.is_empty() {
+            return Ok(Kind::String(s));
+        }
+        if !s.is_empty() {
+            parts.push(StringPart::Literal(s));
+        }
+
+        Ok(Kind::InterpolatedString(parts))
+    }
+
+    /// Eats a `"""..."""` string, where everything up to the closing quotes is
+    /// taken as is: no escapes and no interpolation
+    fn eat_raw_string(&mut self) -> Result<Kind> {
+        let mut s = String::new();
+
+        while let Some(next) = self.next() {
+            if next == '"' && self.peek_is('"') && self.peek_n_is(2, '"', true) {
+                self.next();
+                self.next();
+                return Ok(Kind::String(s));
+            }
+            if next == '\n' {
+                self.line += 1;
+            }
+            s.push(next);
+        }
+
+        Err(LexError::UnterminatedString(self.line).into())
+    }
+
+    /// Eats the rest of an escape sequence, the backslash has already been eaten
+    fn eat_escape(&mut self) -> Result<char> {
+        let ch = match self.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('$') => '$',
+            Some('u') => return self.eat_unicode_escape(),
+            Some(ch) => {
+                let line = self.line;
+                if ch == '\n' {
+                    self.line += 1;
+                }
+                return Err(LexError::InvalidEscape(ch, line).into());
+            }
+            None => return Err(LexError::UnterminatedString(self.line).into()),
+        };
+
+        Ok(ch)
+    }
+
+    /// Eats the `{XXXX}` of a `\u{XXXX}` escape
+    fn eat_unicode_escape(&mut self) -> Result<char> {
+        if !self.peek_is('{') {
+            return Err(LexError::InvalidUnicodeEscape(self.line).into());
+        }
+        self.next();
+
+        let mut hex = String::new();
+        loop {
+            match self.peek() {
+                Some('}') => {
+                    self.next();
+                    break;
+                }
+                Some(ch) if ch.is_ascii_hexdigit() && hex.len() < 6 => {
+                    self.next();
+                    hex.push(ch);
+                }
+                _ => return Err(LexError::InvalidUnicodeEscape(self.line).into()),
+            }
+        }
+
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| LexError::InvalidUnicodeEscape(self.line).into())
+    }
+
+    /// Lex the expression inside `${...}`, expects the `${` has
+    /// already been consumed
+    fn eat_interpolation(&mut self) -> Result<Vec<Token>> {
+        let line = self.line;
+        let start = self.position();
+        let mut src = String::new();
+        let mut depth = 0;
+        let mut in_string = false;
+
+        loop {
+            match self.next() {
+                None => return Err(LexError::UnterminatedString(self.line).into()),
+                Some('}') if depth == 0 && !in_string => break,
+                Some(ch) => {
+                    match ch {
+                        '"' => in_string = !in_string,
+                        // Keep escapes in nested strings so `\"` doesn't end them
+                        '\\' if in_string => {
+                            src.push(ch);
+                            match self.next() {
+                                Some(escaped) => src.push(escaped),
+                                None => return Err(LexError::UnterminatedString(self.line).into()),
+                            }
+                            continue;
+                        }
+                        '{' if !in_string => depth += 1,
+                        '}' if !in_string => depth -= 1,
+                        '\n' => self.line += 1,
+                        _ => {}
+                    }
+                    src.push(ch);
+                }
+            }
+        }
+
+        // Lex the expression on its own, starting from the line it's on
+        let mut lexer = Lexer {
+            src: source::Regular::new(src.chars()),
+            line,
+            offset: start.offset,
+            col: start.col,
+            token_start: start,
+            diagnostics: Vec::new(),
+        };
+        let (tokens, diagnostics) = lexer.lex();
+        // Anything wrong in there has been reported and left an error token
+        // behind for the parser to skip, so there's no need to say it again
+        if !diagnostics.is_empty() {
+            self.diagnostics.extend(diagnostics);
+            return Ok(tokens);
+        }
+        // Just the EOF means there was nothing in there
+        if tokens.len() == 1 {
+            return Err(LexError::InvalidInterpolation(line).into());
+        }
+
+        Ok(tokens)
     }
 
     fn eat_identifier(&mut self, first: char) -> Result<Kind> {
@@ -295,7 +650,7 @@ impl<'a, T: Source> Lexer<T> {
     fn is_separator(c: Option<char>) -> bool {
         matches!(
             c,
-            Some(' ' | '\n' | ';' | ',' | '(' | ')' | '[' | ']') | None
+            Some(' ' | '\n' | ';' | ',' | ':' | '(' | ')' | '[' | ']' | '{' | '}') | None
         )
     }
 
@@ -344,7 +699,7 @@ impl<'a, T: Source> Lexer<T> {
     }
 
     fn eat_block_comment_or_ident(&mut self, first: char) -> Result<Option<Kind>> {
-        match self.eat_keyword_or_ident(first, Kind::OiMate) {
+        match self.eat_keyword(Kind::OiMate, true) {
             Err(_) => Ok(Some(self.eat_identifier(first)?)),
             Ok(_) => {
                 self.eat_block_comment()?;
@@ -447,7 +802,18 @@ impl<'a, T: Source> Lexer<T> {
 // General utilities
 impl<'a, T: Source> Lexer<T> {
     fn next(&mut self) -> Option<char> {
-        self.src.next()
+        let ch = self.src.next()?;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(ch)
+    }
+
+    fn position(&self) -> Position {
+        Position::new(self.offset, self.line, self.col)
     }
 
     fn peek(&mut self) -> Option<char> {
@@ -507,20 +873,80 @@ impl<'a, T: Source> Lexer<T> {
 
 #[derive(Error, Debug)]
 pub enum LexError {
-    #[error("[line {0}] OI MATE! YA NEED AT LEAST 2 'NAH's or 'YEAH's TO MAKE A BOOL!!!")]
+    #[error("OI MATE! YA NEED AT LEAST 2 'NAH's or 'YEAH's TO MAKE A BOOL!!!")]
     TooLittleNahYeahs(usize),
-    #[error("[line {2}] OI MATE! expected {0} but got {1}")]
+    #[error("OI MATE! expected {0} but got {1}")]
     Expected(String, String, usize),
-    #[error("[line {2}] OI MATE! expected {0} but got {1}")]
+    #[error("OI MATE! expected {0} but got {1}")]
     ExpectedCharacter(char, char, usize),
-    #[error("[line {2}] FUCK ME DEAD! EXPECTED ONE OF {0:?} BUT GOT {1}")]
+    #[error("FUCK ME DEAD! EXPECTED ONE OF {0:?} BUT GOT {1}")]
     ExpectedCharacters(Vec<char>, char, usize),
-    #[error("[line {0}] STREWTH! unexpected EOF")]
+    #[error("STREWTH! unexpected EOF")]
     UnexpectedEOF(usize),
-    #[error("[line {1}] BLOODY HELL! UNEXPECTED CHARACTER {0}")]
+    #[error("BLOODY HELL! UNEXPECTED CHARACTER {0}")]
     UnexpectedCharacter(char, usize),
-    #[error("[line {0}] UNTERMINATED STRING YA FUCKWIT!")]
+    #[error("UNTERMINATED STRING YA FUCKWIT!")]
     UnterminatedString(usize),
-    #[error("[line {0}] OI BLUDGER! INVALID NUMBER")]
-    InvalidNumber(usize),
+    #[error("OI BLUDGER! A NUMBER ONLY GETS ONE DECIMAL POINT")]
+    ExtraDecimalPoint(usize),
+    #[error("OI BLUDGER! WHERE'S THE EXPONENT AFTER THE 'e'?")]
+    MissingExponent(usize),
+    #[error("OI BLUDGER! '{0}' NEEDS SOME DIGITS AFTER IT")]
+    MissingDigits(&'static str, usize),
+    #[error("OI BLUDGER! '{0}' ISN'T A DIGIT IN A {1} NUMBER")]
+    InvalidDigit(char, &'static str, usize),
+    #[error("OI BLUDGER! A '_' HAS TO GO BETWEEN TWO DIGITS")]
+    MisplacedSeparator(usize),
+    #[error("OI BLUDGER! THAT NUMBER'S TOO BLOODY BIG")]
+    NumberTooBig(usize),
+    #[error("STREWTH! CAN'T MAKE HEADS OR TAILS OF THAT ${{...}}")]
+    InvalidInterpolation(usize),
+    #[error("FAIR SUCK OF THE SAV! '\\{0}' ISN'T A REAL ESCAPE")]
+    InvalidEscape(char, usize),
+    #[error("YA DROPPED A BOLLOCK! A UNICODE ESCAPE LOOKS LIKE \\u{{1F998}}")]
+    InvalidUnicodeEscape(usize),
+}
+
+impl LexError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::TooLittleNahYeahs(_) => "L001",
+            Self::Expected(..) => "L002",
+            Self::ExpectedCharacter(..) => "L003",
+            Self::ExpectedCharacters(..) => "L004",
+            Self::UnexpectedEOF(_) => "L005",
+            Self::UnexpectedCharacter(..) => "L006",
+            Self::UnterminatedString(_) => "L007",
+            Self::ExtraDecimalPoint(_) => "L008",
+            Self::MissingExponent(_) => "L009",
+            Self::MissingDigits(..) => "L010",
+            Self::InvalidDigit(..) => "L011",
+            Self::MisplacedSeparator(_) => "L012",
+            Self::NumberTooBig(_) => "L013",
+            Self::InvalidInterpolation(_) => "L014",
+            Self::InvalidEscape(..) => "L015",
+            Self::InvalidUnicodeEscape(_) => "L016",
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Self::TooLittleNahYeahs(line)
+            | Self::UnexpectedEOF(line)
+            | Self::UnterminatedString(line)
+            | Self::ExtraDecimalPoint(line)
+            | Self::MissingExponent(line)
+            | Self::MisplacedSeparator(line)
+            | Self::NumberTooBig(line)
+            | Self::InvalidInterpolation(line)
+            | Self::InvalidUnicodeEscape(line)
+            | Self::UnexpectedCharacter(_, line)
+            | Self::MissingDigits(_, line)
+            | Self::InvalidEscape(_, line)
+            | Self::Expected(_, _, line)
+            | Self::ExpectedCharacter(_, _, line)
+            | Self::ExpectedCharacters(_, _, line)
+            | Self::InvalidDigit(_, _, line) => *line,
+        }
+    }
 }
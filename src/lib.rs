@@ -1,60 +1,77 @@
-use anyhow::Result;
+use std::{fs, path::Path};
+
+use ast::Stmt;
+use diagnostic::Diagnostic;
+use lexer::source::{self, Source};
 use parser::parser::Parser;
 use resolver::Resolver;
 use runtime::Interpreter;
+use token::Token;
 
 pub mod ast;
+pub mod diagnostic;
 pub mod lexer;
 pub mod parser;
+pub mod repl;
 pub mod resolver;
 pub mod runtime;
+pub mod span;
 pub mod token;
 pub mod upside_down;
 
-pub fn interpret(src: &str) -> Result<()> {
-    let mut lex = lexer::Lexer::new(lexer::source::Regular::new(src.chars()));
-    let (tokens, _) = lex.lex();
-
-    let mut parser = parser::parser::Parser::new(tokens);
-    let mut stmts = parser.parse()?;
+pub fn interpret(src: &str) -> Result<(), Vec<Diagnostic>> {
+    run(source::Regular::new(src.chars()), Resolver::new())
+}
 
-    if Resolver::new().resolve(&mut stmts) {
-        return Ok(());
-    }
+/// Interpret the file at `path`, resolving any imported files relative to it
+pub fn interpret_file(path: &Path) -> Result<(), Vec<Diagnostic>> {
+    let src = fs::read_to_string(path).map_err(|e| {
+        let msg = format!("STREWTH! COULDN'T READ {}: {}", path.display(), e);
+        vec![Diagnostic::error("E000", msg, None)]
+    })?;
 
-    let mut iptr = Interpreter::new();
-    iptr.interpret(stmts)?;
+    run(source::Regular::new(src.chars()), Resolver::for_file(path))
+}
 
-    Ok(())
+pub fn interpret_upside_down(src: &str) -> Result<(), Vec<Diagnostic>> {
+    run(source::UpsideDown::new(src.chars()), Resolver::new())
 }
 
-pub fn interpret_repl(src: &str, interpreter: &mut Interpreter, parser: &mut Parser) -> Result<()> {
-    let mut lex = lexer::Lexer::new(lexer::source::Regular::new(src.chars()));
-    let (tokens, _) = lex.lex();
+/// Lex and parse a program, handing back everything wrong with it from both
+pub fn parse<T: Source>(src: T) -> Result<Vec<Stmt>, Vec<Diagnostic>> {
+    let (tokens, diagnostics) = lexer::Lexer::new(src).lex();
+    parse_tokens(&mut Parser::new(vec![]), tokens, diagnostics)
+}
 
+/// Parse `tokens` with `parser`, adding what's wrong with them to the
+/// `diagnostics` the lexer found
+pub(crate) fn parse_tokens(
+    parser: &mut Parser,
+    tokens: Vec<Token>,
+    mut diagnostics: Vec<Diagnostic>,
+) -> Result<Vec<Stmt>, Vec<Diagnostic>> {
     parser.reset(tokens);
-    let mut stmts = parser.parse()?;
+    let (stmts, parse_diagnostics) = parser.parse_partial();
+    diagnostics.extend(parse_diagnostics);
+    // Lexer and parser problems in the order they come in the source
+    diagnostics.sort_by_key(|d| match d.span {
+        Some(span) => (span.start.line, span.start.offset),
+        None => (d.line().unwrap_or(0), 0),
+    });
 
-    if Resolver::new().resolve(&mut stmts) {
-        return Ok(());
+    if diagnostics.is_empty() {
+        Ok(stmts)
+    } else {
+        Err(diagnostics)
     }
-
-    interpreter.interpret(stmts)
 }
 
-pub fn interpret_upside_down(src: &str) -> Result<()> {
-    let mut lex = lexer::Lexer::new(lexer::source::UpsideDown::new(src.chars()));
-    let (tokens, _) = lex.lex();
-
-    let mut parser = parser::parser::Parser::new(tokens);
-    let mut stmts = parser.parse()?;
-
-    if Resolver::new().resolve(&mut stmts) {
-        return Ok(());
-    }
-
-    let mut iptr = Interpreter::new();
-    iptr.interpret(stmts)?;
+/// Take a program through every stage, stopping at the first one that
+/// finds something wrong. Lexing and parsing count as one, so all the
+/// mistakes in how it's written come out together
+fn run<T: Source>(src: T, mut resolver: Resolver) -> Result<(), Vec<Diagnostic>> {
+    let mut stmts = parse(src)?;
+    resolver.resolve(&mut stmts)?;
 
-    Ok(())
+    Interpreter::new().interpret(stmts)
 }
@@ -1,12 +1,19 @@
 #[cfg(target_os = "emscripten")]
 use std::{ffi::CString, mem, os::raw::c_char};
 
+use aussie_plus_plus::diagnostic::{Diagnostic, Renderer};
+
 #[cfg(not(target_os = "emscripten"))]
 use std::{
-    fs,
-    io::{self, BufRead},
+    env, fs,
+    io::{self, IsTerminal},
     path::PathBuf,
 };
+
+#[cfg(not(target_os = "emscripten"))]
+use aussie_plus_plus::repl::{Repl, Status};
+#[cfg(not(target_os = "emscripten"))]
+use rustyline::{error::ReadlineError, Editor};
 #[cfg(not(target_os = "emscripten"))]
 use structopt::StructOpt;
 #[cfg(not(target_os = "emscripten"))]
@@ -16,30 +23,49 @@ struct Opt {
     /// Path to input file
     #[structopt(name = "File", parse(from_os_str))]
     filepath: Option<PathBuf>,
+
+    /// Don't colour error messages, for when they're going to a log
+    #[structopt(long)]
+    plain: bool,
 }
 #[cfg(not(target_os = "emscripten"))]
 fn main() {
     let opt = Opt::from_args();
-    let code: String;
+    let colour = !opt.plain && env::var_os("NO_COLOR").is_none() && io::stderr().is_terminal();
+
     if let Some(filepath) = opt.filepath {
-        code = fs::read_to_string(filepath).expect("failed to read file");
-        aussie_plus_plus::interpret(code.as_str()).unwrap();
+        if let Err(diagnostics) = aussie_plus_plus::interpret_file(&filepath) {
+            let src = fs::read_to_string(&filepath).unwrap_or_default();
+            report(&diagnostics, Renderer::new(&src).with_colour(colour));
+        }
         println!("CHEERS C***!");
         return;
     }
 
-    let stdin = io::stdin();
-    let mut i = aussie_plus_plus::runtime::Interpreter::new();
-    let mut p = aussie_plus_plus::parser::parser::Parser::new(vec![]);
-    for line in stdin.lock().lines() {
-        let line = line.unwrap();
-        match aussie_plus_plus::interpret_repl(line.as_str(), &mut i, &mut p) {
-            Ok(stmts) => stmts,
-            Err(_) => {
-                // eprintln!("Failed to run: {}", e);
-                return;
+    let mut repl = Repl::new().with_colour(colour);
+    let mut editor = Editor::<()>::new();
+    let history = dirs::home_dir().map(|home| home.join(".aussie_history"));
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        let prompt = if repl.is_unfinished() { ".. " } else { "> " };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                editor.add_history_entry(line.as_str());
+                if repl.feed(&line) == Status::Quit {
+                    break;
+                }
             }
-        };
+            // Ctrl-C throws away the current entry, Ctrl-D quits
+            Err(ReadlineError::Interrupted) => repl.cancel(),
+            Err(_) => break,
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
     }
     println!("CHEERS C***!");
 }
@@ -47,15 +73,30 @@ fn main() {
 #[cfg(target_os = "emscripten")]
 fn main() {}
 
+/// Print whatever went wrong running a program
+fn report(diagnostics: &[Diagnostic], renderer: Renderer) {
+    for diagnostic in diagnostics {
+        eprintln!("{}", renderer.render(diagnostic));
+    }
+}
+
 #[cfg(target_os = "emscripten")]
 /// # Safety
 #[no_mangle]
 pub unsafe extern "C" fn interpret(src: *mut c_char, upside_down: bool) -> usize {
     let code = CString::from_raw(src).to_str().unwrap().to_string();
     if !upside_down {
-        let _ = aussie_plus_plus::interpret(&code);
-    } else {
-        let _ = aussie_plus_plus::interpret_upside_down(&code);
+        if let Err(diagnostics) = aussie_plus_plus::interpret(&code) {
+            report(&diagnostics, Renderer::new(&code));
+        }
+    } else if let Err(diagnostics) = aussie_plus_plus::interpret_upside_down(&code) {
+        // Spans point into the program the right way up
+        let flipped: String = code
+            .chars()
+            .rev()
+            .map(aussie_plus_plus::upside_down::rightside_up)
+            .collect();
+        report(&diagnostics, Renderer::new(&flipped));
     }
     0
 }
@@ -4,26 +4,99 @@ use crate::token::{Kind, Token};
 
 #[derive(Error, Debug)]
 pub enum ParseError {
-    #[error("[line {0}] {1}")]
+    #[error("{1}")]
     Any(usize, String),
-    #[error("[line {2}] OI MATE! EXPECTED {0} BUT GOT '{1}'")]
-    UnexpectedToken(Kind, Kind, usize),
-    #[error("[line {2}] OI MATE! EXPECTED {0:?} BUT GOT '{1}'")]
-    ExpectedTokens(Vec<Kind>, Kind, usize),
-    #[error("[line {}] MISSING EXPR ENDED WITH '{}'", .0.line(), .0.kind())]
+    #[error("OI MATE! EXPECTED {} BUT GOT '{}'", .0, .1.kind())]
+    UnexpectedToken(Kind, Token),
+    #[error("OI MATE! EXPECTED {:?} BUT GOT '{}'", .0, .1.kind())]
+    ExpectedTokens(Vec<Kind>, Token),
+    #[error("MISSING EXPR ENDED WITH '{}'", .0.kind())]
     MissingExpr(Token),
-    #[error("[line {}] HEY FUCKWIT! WHY YA FACKIN TRYNA ASSIGN TO A {}", .0.line(), .0.kind())]
+    #[error("HEY FUCKWIT! WHY YA FACKIN TRYNA ASSIGN TO A {}", .0.kind())]
     InvalidAssigment(Token),
-    #[error("[line {0}] MATE, THAT'S TOO MANY ARGUMENTS (max 255)")]
+    #[error("MATE, THAT'S TOO MANY ARGUMENTS (max 255)")]
     TooManyArguments(usize),
-    #[error("[line {0}] TOO MANY DEFAULT BRANCHES IN MATCH STATEMENT, YA DAFT BUGGER")]
+    #[error("TOO MANY DEFAULT BRANCHES IN MATCH STATEMENT, YA DAFT BUGGER")]
     TooManyMatchDefaultBranches(usize),
-    #[error("[line {0}] CAN YA FUKING COUNT, MATE? INVALID RANGE {1} {2}")]
+    #[error("CAN YA FUKING COUNT, MATE? INVALID RANGE {1} {2}")]
     InvalidRange(usize, String, String),
-    #[error("[line {0}] EXPECTED NUMBER, STRING, BOOLEAN, NIL, OR IDENTIFIER BUT GOT '{1}'")]
-    ExpectPrimary(usize, Kind),
+    #[error(
+        "EXPECTED NUMBER, STRING, BOOLEAN, NIL, LIST, MAP, OR IDENTIFIER BUT GOT '{}'",
+        .0.kind()
+    )]
+    ExpectPrimary(Token),
     #[error("YA DAFT BUGGER! YA DIDN'T WRITE \"G'DAY MATE!\" TO START PROGRAM!!")]
     ExpectProgramStart,
-    #[error("[line {0}] OI CUNT! INVALID WHATABOUT, NOT IN AN A RECKON YA BLUDGER!")]
+    #[error("OI CUNT! INVALID WHATABOUT, NOT IN AN A RECKON YA BLUDGER!")]
     InvalidWhatabout(usize),
+    #[error("OI! ONLY A WALKABOUT CAN HAVE A LABEL, YA GALAH!")]
+    InvalidLabel(usize),
+    #[error("OI MATE, DID YA FORGET A SEMI-COLON BEFORE THAT '{}'??", .0.kind())]
+    MissingSemicolon(Token),
+    #[error("OI MATE! EXPECTED A NAME BEFORE {}", .0.kind().to_string().to_uppercase())]
+    MissingLoopVar(Token),
+}
+
+impl ParseError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Any(..) => "P001",
+            Self::UnexpectedToken(..) => "P002",
+            Self::ExpectedTokens(..) => "P003",
+            Self::MissingExpr(_) => "P004",
+            Self::InvalidAssigment(_) => "P005",
+            Self::TooManyArguments(_) => "P006",
+            Self::TooManyMatchDefaultBranches(_) => "P007",
+            Self::InvalidRange(..) => "P008",
+            Self::ExpectPrimary(..) => "P009",
+            Self::ExpectProgramStart => "P010",
+            Self::InvalidWhatabout(_) => "P011",
+            Self::InvalidLabel(_) => "P012",
+            Self::MissingSemicolon(_) => "P013",
+            Self::MissingLoopVar(_) => "P014",
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::ExpectProgramStart => None,
+            Self::MissingExpr(tok)
+            | Self::InvalidAssigment(tok)
+            | Self::UnexpectedToken(_, tok)
+            | Self::ExpectedTokens(_, tok)
+            | Self::ExpectPrimary(tok)
+            | Self::MissingSemicolon(tok)
+            | Self::MissingLoopVar(tok) => Some(tok.line()),
+            Self::Any(line, _)
+            | Self::TooManyArguments(line)
+            | Self::TooManyMatchDefaultBranches(line)
+            | Self::InvalidRange(line, _, _)
+            | Self::InvalidWhatabout(line)
+            | Self::InvalidLabel(line) => Some(*line),
+        }
+    }
+
+    /// The token the parser choked on, if there was one
+    pub fn token(&self) -> Option<&Token> {
+        match self {
+            Self::MissingExpr(tok)
+            | Self::InvalidAssigment(tok)
+            | Self::UnexpectedToken(_, tok)
+            | Self::ExpectedTokens(_, tok)
+            | Self::ExpectPrimary(tok)
+            | Self::MissingSemicolon(tok)
+            | Self::MissingLoopVar(tok) => Some(tok),
+            _ => None,
+        }
+    }
+
+    /// Whether the error came from running out of tokens
+    pub fn at_eof(&self) -> bool {
+        match self {
+            Self::InvalidAssigment(_) | Self::MissingSemicolon(_) | Self::MissingLoopVar(_) => {
+                false
+            }
+            _ => self.token().is_some_and(|tok| tok.kind() == Kind::EOF),
+        }
+    }
 }
@@ -1,17 +1,23 @@
-use anyhow::{anyhow, Result};
+use std::mem;
+
+use anyhow::Result;
 
 use crate::ast::{
-    FnDecl, ForLoop, Ident, If, LogicalOp, Match, MatchBranch, Pattern, RangeBound, Stmt, Var,
-    VarDecl, WhileLoop,
+    FnDecl, ForEachLoop, ForLoop, Ident, If, LogicalOp, Match, MatchBranch, ModuleImport, Pattern,
+    RangeBound, Stmt, TryCatch, Var, VarDecl, WhileLoop,
 };
 use crate::runtime::{Value, MAX_ARITY};
 use crate::{
     ast::{BinaryOp, Expr, ExprNode, UnaryOp},
-    token::{Kind, Token},
+    diagnostic::Diagnostic,
+    span::{Position, Span},
+    token::{Kind, StringPart, Token},
 };
 
 use super::error::ParseError;
 
+type MatchBranches<T> = (Vec<MatchBranch<T>>, Option<MatchBranch<T>>);
+
 /// Advance token if match succeeds
 macro_rules! match_toks {
     ($self:ident, $other:pat => $other_result:expr, $($pat:pat => $result:expr),*) => {
@@ -35,6 +41,9 @@ pub struct Parser {
     // To help discriminate boomerangs vs. gt/lt
     inside_block: usize,
     inside_if: usize,
+
+    // Errors that have been recovered from
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Parser {
@@ -45,50 +54,147 @@ impl Parser {
             current: 0,
             inside_block: 0,
             inside_if: 0,
+            diagnostics: Vec::new(),
         }
     }
 
     pub fn reset(&mut self, tokens: Vec<Token>) {
         self.current = 0;
         self.tokens = tokens;
+        self.inside_block = 0;
+        self.inside_if = 0;
     }
 
-    pub fn parse(&mut self) -> Result<Vec<Stmt>> {
+    /// Whether `tokens` run out part way through a statement, like a block
+    /// missing its closing boomerang, so more input could still finish them
+    pub fn is_unfinished(&self, tokens: &[Token]) -> bool {
+        let mut parser = Self::new(tokens.to_vec());
+        if !self.consumed_start && parser.consume_program_start().is_err() {
+            return false;
+        }
+
+        while !parser.is_at_end() {
+            if let Err(e) = parser.recover(Self::declaration) {
+                // A real mistake earlier on won't be fixed by more input
+                return parser.diagnostics.is_empty()
+                    && e.downcast_ref::<ParseError>()
+                        .is_some_and(ParseError::at_eof);
+            }
+        }
+
+        false
+    }
+
+    pub fn parse(&mut self) -> std::result::Result<Vec<Stmt>, Vec<Diagnostic>> {
+        let (stmts, diagnostics) = self.parse_partial();
+        if diagnostics.is_empty() {
+            Ok(stmts)
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Parse as much as possible, handing back every statement that made
+    /// sense along with what was wrong with the rest
+    pub fn parse_partial(&mut self) -> (Vec<Stmt>, Vec<Diagnostic>) {
         let mut stmts: Vec<Stmt> = Vec::new();
-        let mut had_error = false;
 
         if !self.consumed_start {
             match self.consume_program_start() {
                 Ok(_) => {
                     self.consumed_start = true;
                 }
-                Err(e) => {
-                    had_error = true;
-                    eprintln!("{:?}", e)
-                }
+                Err(e) => self.error(e),
             }
         }
 
         while !self.is_at_end() {
-            match self.declaration() {
-                Ok(stmt) => {
-                    stmts.push(stmt);
-                }
-                Err(e) => {
-                    had_error = true;
-                    eprintln!("{:?}", e);
+            match self.recover(Self::declaration) {
+                Ok(Some(stmt)) => stmts.push(stmt),
+                Ok(None) => {}
+                Err(e) => self.error(e),
+            }
+        }
+
+        (stmts, mem::take(&mut self.diagnostics))
+    }
+
+    /// Run `parse`, and if it fails note the error and skip to the start of
+    /// the next statement, so parsing can carry on. Errors at the end of the
+    /// tokens are handed back, since there's nothing left to skip to
+    fn recover<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        let start = self.current;
+        let inside_block = self.inside_block;
+        let inside_if = self.inside_if;
+
+        match parse(self) {
+            Ok(val) => Ok(Some(val)),
+            Err(e) if self.is_at_end() => Err(e),
+            Err(e) => {
+                // Whatever failed might not have got to undo these
+                self.inside_block = inside_block;
+                self.inside_if = inside_if;
+                self.error(e);
+                self.synchronize(start);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Skip past the tokens of a statement that failed to parse, stopping
+    /// after a `;`, or before a `>` closing a block or a keyword that starts
+    /// a new statement
+    fn synchronize(&mut self, start: usize) {
+        // Always skip something, so the same tokens can't fail forever
+        if self.current == start {
+            let _ = self.advance();
+        }
+
+        while !self.is_at_end() {
+            if self.previous().kind() == Kind::Semicolon {
+                return;
+            }
+
+            match self.peek().kind() {
+                Kind::RightBoomerang if self.inside_block > 0 => return,
+                Kind::IReckon
+                | Kind::IFullyReckon
+                | Kind::HardYakkaFor
+                | Kind::YaReckon
+                | Kind::Whatabout
+                | Kind::Gimme
+                | Kind::Bail
+                | Kind::GiveItABurl
+                | Kind::ChuckAWobbly
+                | Kind::MateFuckThis
+                | Kind::ShellBeRight
+                | Kind::FuckinPiker
+                | Kind::Cheers
+                | Kind::Import => return,
+                _ => {
+                    let _ = self.advance();
                 }
             }
         }
+    }
 
-        if had_error {
-            Err(anyhow!("failed to parse"))
-        } else {
-            Ok(stmts)
+    fn error(&mut self, e: anyhow::Error) {
+        // The lexer has already reported what's wrong with its error tokens
+        if !is_lexer_error(&e) {
+            self.diagnostics.push((&e).into());
         }
     }
 
     fn declaration(&mut self) -> Result<Stmt> {
+        if matches!(self.peek().kind(), Kind::Ident(_)) && self.peek_n(1).kind() == Kind::Colon {
+            return self.labelled_loop();
+        }
+
+        // `THE HARD YAKKA FOR (...)` is a lambda, so parse it as an expression
+        if self.peek().kind() == Kind::HardYakkaFor && self.peek_n(1).kind() == Kind::LeftParen {
+            return self.statement();
+        }
+
         match_toks!(self,
             _ => self.statement(),
             (Kind::IReckon | Kind::IFullyReckon) => self.var_decl_or_loop(),
@@ -101,18 +207,15 @@ impl Parser {
 
         if self.match_tok(Kind::IllHaveA) {
             self.consume(Kind::Walkabout)?;
-            return self.loops(None);
+            return self.loops(None, None);
         }
 
         let ident = self.consume_ident()?;
 
         match_toks!(self,
-            other => {
-                let line = self.peek().line();
-                Err(
-                    ParseError::ExpectedTokens(vec![Kind::Semicolon, Kind::Assign], other, line).into(),
-                )
-            },
+            _ => Err(
+                ParseError::ExpectedTokens(vec![Kind::Semicolon, Kind::Assign], self.peek()).into(),
+            ),
             Kind::Semicolon => {
                 Ok(Stmt::VarDecl(VarDecl{ident, initializer:None, immutable}))
             },
@@ -123,14 +226,43 @@ impl Parser {
             },
             Kind::Isa => {
                 self.consume(Kind::Walkabout)?;
-                self.loops(Some(ident))
+                self.loops(Some(ident), None)
             }
         )
     }
 
+    /// Parse a loop prefixed with a label, e.g. `outer: I RECKON x IS A WALKABOUT ...`
+    fn labelled_loop(&mut self) -> Result<Stmt> {
+        let label = self.consume_ident()?;
+        self.consume(Kind::Colon)?;
+
+        if !self.match_tok(Kind::IReckon) && !self.match_tok(Kind::IFullyReckon) {
+            return Err(ParseError::InvalidLabel(label.line()).into());
+        }
+
+        if self.match_tok(Kind::IllHaveA) {
+            self.consume(Kind::Walkabout)?;
+            return self.loops(None, Some(label));
+        }
+
+        let ident = self.consume_ident()?;
+        if !self.match_tok(Kind::Isa) {
+            return Err(ParseError::InvalidLabel(label.line()).into());
+        }
+        self.consume(Kind::Walkabout)?;
+        self.loops(Some(ident), Some(label))
+    }
+
     fn fn_decl(&mut self) -> Result<Stmt> {
         let name = self.consume_ident()?;
         self.consume(Kind::Is)?;
+
+        Ok(Stmt::FnDecl(self.function(name)?))
+    }
+
+    /// Parse the parameters and body of a function, shared by
+    /// function declarations and lambdas
+    fn function(&mut self, name: Ident) -> Result<FnDecl> {
         let mut params: Vec<Ident> = Vec::new();
 
         self.consume(Kind::LeftParen)?;
@@ -161,22 +293,33 @@ impl Parser {
         };
         self.inside_block -= 1;
 
-        Ok(Stmt::FnDecl(FnDecl::new(name, params, body)))
+        Ok(FnDecl::new(name, params, body))
     }
 
-    fn loops(&mut self, ident: Option<Ident>) -> Result<Stmt> {
+    fn loops(&mut self, ident: Option<Ident>, label: Option<Ident>) -> Result<Stmt> {
         match_toks!(self,
-            _other => {
-                todo!()
+            _ => {
+                let e = ParseError::ExpectedTokens(
+                    vec![Kind::From, Kind::Through, Kind::Until],
+                    self.peek(),
+                );
+                // Skip the rest of the loop, body and all, so recovering
+                // doesn't trip over the `>` closing it
+                while !self.is_at_end() && !self.check(Kind::LeftBoomerang) && !self.check(Kind::Semicolon) {
+                    let _ = self.advance();
+                }
+                if self.check(Kind::LeftBoomerang) {
+                    self.statement()?;
+                }
+                Err(e.into())
             },
             Kind::From => {
-                let ident = ident.unwrap();
+                let keyword = self.previous();
                 let start = match_toks!(self,
-                    k =>
+                    _ =>
                     return Err(ParseError::ExpectedTokens(
                         vec![Kind::LeftParen, Kind::LeftBracket],
-                        k,
-                        ident.line(),
+                        self.peek(),
                     ).into()),
                     Kind::LeftParen => {
                         RangeBound::Exclusive(self.expression()?)
@@ -189,11 +332,10 @@ impl Parser {
                 let end = {
                     let expr = self.expression()?;
                     match_toks!(self,
-                        k =>
+                        _ =>
                         return Err(ParseError::ExpectedTokens(
                             vec![Kind::RightParen, Kind::RightBracket],
-                            k,
-                            ident.line(),
+                            self.peek(),
                         ).into()),
                         Kind::RightParen => {
                             RangeBound::Exclusive(expr)
@@ -204,9 +346,29 @@ impl Parser {
                     )
                 };
 
+                let step = if self.match_tok(Kind::Hoppin) {
+                    Some(self.loop_header_expr()?)
+                } else {
+                    None
+                };
+
                 let body = self.statement()?;
+                // The rest of the loop has been parsed first so that
+                // recovering from a missing name carries on after it
+                let ident = ident.ok_or(ParseError::MissingLoopVar(keyword))?;
 
-                Ok(Stmt::For(Box::new(ForLoop::new((ident, usize::MAX).into(), (start, end), vec![body]))))
+                let for_loop = ForLoop::new_with_step((ident, usize::MAX).into(), (start, end), step, vec![body]);
+                Ok(Stmt::For(Box::new(ForLoop { label, ..for_loop })))
+            },
+            Kind::Through => {
+                let keyword = self.previous();
+                let iterable = self.loop_header_expr()?;
+
+                let body = self.statement()?;
+                let ident = ident.ok_or(ParseError::MissingLoopVar(keyword))?;
+
+                let for_each = ForEachLoop::new((ident, usize::MAX).into(), iterable, vec![body]);
+                Ok(Stmt::ForEach(Box::new(ForEachLoop { label, ..for_each })))
             },
             Kind::Until => {
                 self.consume(Kind::LeftParen)?;
@@ -215,11 +377,18 @@ impl Parser {
 
                 let body = self.statement()?;
 
-                Ok(Stmt::While(Box::new(WhileLoop::new(cond, vec![body]))))
+                let while_loop = WhileLoop::new(cond, vec![body]);
+                Ok(Stmt::While(Box::new(WhileLoop { label, ..while_loop })))
             }
         )
     }
 
+    /// An expression just before a loop's body, parsed at `term` precedence
+    /// so the `<` opening the body isn't mistaken for a comparison
+    fn loop_header_expr(&mut self) -> Result<ExprNode> {
+        self.term()
+    }
+
     fn statement(&mut self) -> Result<Stmt> {
         match_toks!(self,
             _ => self.expression_statement(),
@@ -233,10 +402,24 @@ impl Parser {
             Kind::Whatabout => self.condition_statement(true),
             Kind::Gimme => self.print_statement(),
             Kind::Bail => self.return_statement(),
+            Kind::GiveItABurl => self.try_statement(),
+            Kind::ChuckAWobbly => {
+                let tok = self.previous();
+                let expr = self.expression()?;
+                self.consume(Kind::Semicolon)?;
+                Ok(Stmt::Throw(tok, expr))
+            },
             Kind::MateFuckThis => {
                 let tok = self.previous();
+                let label = self.loop_label()?;
                 self.consume(Kind::Semicolon)?;
-                Ok(Stmt::Break(tok))
+                Ok(Stmt::Break(tok, label))
+            },
+            Kind::ShellBeRight => {
+                let tok = self.previous();
+                let label = self.loop_label()?;
+                self.consume(Kind::Semicolon)?;
+                Ok(Stmt::Continue(tok, label))
             },
             Kind::FuckinPiker => self.exit_statement(true),
             Kind::Cheers => self.exit_statement(false),
@@ -244,7 +427,40 @@ impl Parser {
         )
     }
 
+    fn try_statement(&mut self) -> Result<Stmt> {
+        let body = self.statement()?;
+
+        self.consume(Kind::Strewth)?;
+        let var = self.consume_ident()?;
+        let handler = self.statement()?;
+
+        Ok(Stmt::Try(Box::new(TryCatch::new(
+            vec![body],
+            var,
+            vec![handler],
+        ))))
+    }
+
+    /// Parse the optional label naming the loop to break out of or continue
+    fn loop_label(&mut self) -> Result<Option<Ident>> {
+        if matches!(self.peek().kind(), Kind::Ident(_)) {
+            Ok(Some(self.consume_ident()?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn import_statement(&mut self) -> Result<Stmt> {
+        if let Kind::String(path) = self.peek().kind() {
+            let tok = self.advance();
+            self.consume(Kind::Semicolon)?;
+            let import = ModuleImport {
+                span: tok.span(),
+                ..ModuleImport::new(path, tok.line())
+            };
+            return Ok(Stmt::ImportModule(Box::new(import)));
+        }
+
         let ident = self.consume_ident()?;
         self.consume(Kind::Semicolon)?;
 
@@ -264,7 +480,9 @@ impl Parser {
         let mut vec: Vec<Stmt> = Vec::new();
 
         while !self.match_tok(Kind::RightBoomerang) {
-            vec.push(self.declaration()?);
+            if let Some(stmt) = self.recover(Self::declaration)? {
+                vec.push(stmt);
+            }
         }
 
         Ok(Stmt::Block(vec))
@@ -282,7 +500,8 @@ impl Parser {
             self.inside_if -= 1;
             return Ok(Stmt::If(If {
                 // Make it an always true if condition so it always executes
-                cond: ExprNode::new(Expr::Literal(Value::Bool(true)), self.previous().line()),
+                cond: ExprNode::new(Expr::Literal(Value::Bool(true)), self.previous().line())
+                    .with_span(self.previous().span()),
                 then: Box::new(self.statement()?),
                 else_: None,
             }));
@@ -291,7 +510,6 @@ impl Parser {
         let cond = self.expression()?;
         let peek = self.peek();
         let kind = peek.kind();
-        let line = peek.line();
 
         match kind {
             Kind::QuestionMark => {
@@ -310,18 +528,13 @@ impl Parser {
                 self.consume(Kind::Isa)?;
                 self.consume(Kind::LeftBoomerang)?;
                 self.inside_block += 1;
-                let ret = self.match_branches();
+                let ret = self.match_branches(|parser| Ok(parser.statement()?.into()));
                 self.inside_block -= 1;
                 let (branches, default) = ret?;
 
-                Ok(Stmt::Match(Match::new(cond, branches, default)))
-            }
-            other => {
-                Err(
-                    ParseError::ExpectedTokens(vec![Kind::QuestionMark, Kind::Isa], other, line)
-                        .into(),
-                )
+                Ok(Stmt::Match(Box::new(Match::new(cond, branches, default))))
             }
+            _ => Err(ParseError::ExpectedTokens(vec![Kind::QuestionMark, Kind::Isa], peek).into()),
         }
     }
 
@@ -333,38 +546,24 @@ impl Parser {
         Ok(stmt)
     }
 
-    fn match_branches(&mut self) -> Result<(Vec<MatchBranch>, Option<MatchBranch>)> {
-        let mut vec: Vec<MatchBranch> = Vec::new();
-        let mut default: Option<MatchBranch> = None;
+    /// Parse the branches of a match, using `body` to parse what
+    /// comes after each `~`
+    fn match_branches<T>(
+        &mut self,
+        body: impl Fn(&mut Self) -> Result<T>,
+    ) -> Result<MatchBranches<T>> {
+        let mut vec: Vec<MatchBranch<T>> = Vec::new();
+        let mut default: Option<MatchBranch<T>> = None;
 
         while !self.match_tok(Kind::RightBoomerang) {
-            let peek = self.peek();
-            let val: Option<Pattern> = peek.clone().into();
-            let val = if let Some(val) = val {
-                let _ = self.advance();
-                val
-            } else {
-                return Err(ParseError::ExpectedTokens(
-                    vec![
-                        Kind::Number(420.into()),
-                        Kind::String("any string literal".into()),
-                        Kind::True,
-                        Kind::False,
-                        Kind::BuggerAll,
-                        Kind::Ident("any identifier".into()),
-                    ],
-                    peek.kind(),
-                    peek.line(),
-                )
-                .into());
+            let branch = match self.recover(|parser| parser.match_branch(&body))? {
+                Some(branch) => branch,
+                None => continue,
             };
 
-            self.consume(Kind::Tilde)?;
-
-            let body: Vec<Stmt> = self.statement()?.into();
-
-            let branch = MatchBranch::new(val.clone(), body, peek.line());
-            if let Pattern::Var(_) = val {
+            // Guarded catch-alls might not match, so they aren't the default
+            let is_default = matches!(branch.pat, Pattern::Var(_)) && branch.guard.is_none();
+            if is_default {
                 if default.is_some() {
                     return Err(ParseError::TooManyMatchDefaultBranches(branch.line()).into());
                 }
@@ -377,6 +576,176 @@ impl Parser {
         Ok((vec, default))
     }
 
+    fn match_branch<T>(
+        &mut self,
+        body: &impl Fn(&mut Self) -> Result<T>,
+    ) -> Result<MatchBranch<T>> {
+        let start = self.peek().span();
+        let line = self.peek().line();
+        let mut pats = vec![self.pattern()?];
+        while self.match_tok(Kind::Comma) {
+            pats.push(self.pattern()?);
+        }
+
+        let val = if pats.len() == 1 {
+            pats.remove(0)
+        } else if pats.iter().any(|pat| matches!(pat, Pattern::Var(_))) {
+            return Err(ParseError::Any(
+                line,
+                "OI! A CATCH-ALL HAS TO BE ON ITS OWN, YA GALAH".into(),
+            )
+            .into());
+        } else {
+            Pattern::Alternatives(pats)
+        };
+
+        let guard = if self.match_tok(Kind::YaReckon) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.consume(Kind::Tilde)?;
+
+        let body = body(self)?;
+
+        Ok(MatchBranch::new_with_guard(val, guard, body, line)
+            .with_span(start.to(self.previous().span())))
+    }
+
+    fn interpolation(&mut self, parts: Vec<StringPart>, string: &Token) -> Result<Expr> {
+        let mut exprs: Vec<ExprNode> = Vec::new();
+
+        for part in parts {
+            match part {
+                StringPart::Literal(s) => exprs.push(
+                    ExprNode::new(Expr::Literal(s.into()), string.line()).with_span(string.span()),
+                ),
+                StringPart::Expr(tokens) => {
+                    let mut parser = Parser::new(tokens);
+                    let expr = parser.expression()?;
+                    if !parser.is_at_end() {
+                        let tok = parser.peek();
+                        return Err(ParseError::Any(
+                            tok.line(),
+                            format!(
+                                "OI! ONLY ONE EXPRESSION IN A ${{...}}, NOT '{}'",
+                                tok.kind()
+                            ),
+                        )
+                        .into());
+                    }
+                    exprs.push(expr);
+                }
+            }
+        }
+
+        Ok(Expr::Interpolation(exprs))
+    }
+
+    /// Parse a match expression, expects `YA RECKON` has already been consumed
+    fn match_expression(&mut self) -> Result<Expr> {
+        let val = self.expression()?;
+        self.consume(Kind::Isa)?;
+        self.consume(Kind::LeftBoomerang)?;
+
+        self.inside_block += 1;
+        let ret = self.match_branches(|parser| {
+            let expr = parser.expression()?;
+            parser.consume(Kind::Semicolon)?;
+            Ok(expr)
+        });
+        self.inside_block -= 1;
+        let (branches, default) = ret?;
+
+        Ok(Expr::Match(Box::new(Match::new(val, branches, default))))
+    }
+
+    /// Parse a single match pattern: a literal, a range or a catch-all
+    fn pattern(&mut self) -> Result<Pattern> {
+        let peek = self.peek();
+
+        if matches!(peek.kind(), Kind::LeftBracket | Kind::LeftParen) {
+            return self.range_pattern();
+        }
+        if peek.kind() == Kind::Minus {
+            return self.signed_pattern_number();
+        }
+
+        let val: Option<Pattern> = peek.clone().into();
+        if let Some(val) = val {
+            let _ = self.advance();
+            return Ok(val);
+        }
+
+        Err(ParseError::ExpectedTokens(
+            vec![
+                Kind::Number(420.into()),
+                Kind::String("any string literal".into()),
+                Kind::True,
+                Kind::False,
+                Kind::BuggerAll,
+                Kind::LeftBracket,
+                Kind::LeftParen,
+                Kind::Ident("any identifier".into()),
+            ],
+            peek,
+        )
+        .into())
+    }
+
+    /// Parse a range pattern in interval notation, e.g. `[1 to 10)`
+    fn range_pattern(&mut self) -> Result<Pattern> {
+        let inclusive_start = self.advance().kind() == Kind::LeftBracket;
+        let start = self.range_pattern_bound()?;
+        self.consume(Kind::To)?;
+        let end = self.range_pattern_bound()?;
+
+        let start = if inclusive_start {
+            RangeBound::Inclusive(start)
+        } else {
+            RangeBound::Exclusive(start)
+        };
+        let end = match_toks!(self,
+            _ => return Err(ParseError::ExpectedTokens(
+                vec![Kind::RightParen, Kind::RightBracket],
+                self.peek(),
+            ).into()),
+            Kind::RightParen => RangeBound::Exclusive(end),
+            Kind::RightBracket => RangeBound::Inclusive(end)
+        );
+
+        Ok(Pattern::Range(start, end))
+    }
+
+    fn range_pattern_bound(&mut self) -> Result<f64> {
+        match self.signed_pattern_number()? {
+            Pattern::Integer(n) => Ok(n as f64),
+            Pattern::Number(n) => Ok(n),
+            _ => unreachable!("signed_pattern_number only returns numbers"),
+        }
+    }
+
+    fn signed_pattern_number(&mut self) -> Result<Pattern> {
+        if !self.match_tok(Kind::Minus) {
+            return self.pattern_number();
+        }
+        match self.pattern_number()? {
+            Pattern::Integer(n) => Ok(Pattern::Integer(-n)),
+            Pattern::Number(n) => Ok(Pattern::Number(-n)),
+            _ => unreachable!("pattern_number only returns numbers"),
+        }
+    }
+
+    fn pattern_number(&mut self) -> Result<Pattern> {
+        let tok = self.advance();
+        match tok.kind() {
+            Kind::Number(n) => Ok(Pattern::Number(n)),
+            Kind::Integer(n) => Ok(Pattern::Integer(n)),
+            _ => Err(ParseError::UnexpectedToken(Kind::Number(420.into()), tok).into()),
+        }
+    }
+
     fn return_statement(&mut self) -> Result<Stmt> {
         let tok = self.previous();
         if self.match_tok(Kind::Semicolon) {
@@ -402,20 +771,40 @@ impl Parser {
 
     fn assignment(&mut self) -> Result<ExprNode> {
         let expr = self.or()?;
-        match self.peek().kind {
-            Kind::Assign => {
-                let equals = self.advance();
-                let initializer = self.expression()?;
-
-                if let Expr::Var(v) = expr.expr() {
-                    let assign = Expr::Assign(v.clone(), Box::new(initializer));
-                    return Ok(ExprNode::new(assign, expr.line()));
-                }
-
-                Err(ParseError::InvalidAssigment(equals).into())
+        let op = match self.peek().kind {
+            Kind::Assign => None,
+            Kind::PlusEqual => Some(BinaryOp::Plus),
+            Kind::MinusEqual => Some(BinaryOp::Minus),
+            Kind::AsteriskEqual => Some(BinaryOp::Multiply),
+            Kind::SlashEqual => Some(BinaryOp::Divide),
+            Kind::ModuloEqual => Some(BinaryOp::Modulo),
+            _ => return Ok(expr),
+        };
+        let equals = self.advance();
+        let initializer = Box::new(self.expression()?);
+        let line = expr.line();
+        let span = expr.span().to(initializer.span());
+
+        let assign = match (expr.expr(), op) {
+            (Expr::Var(v), None) => Expr::Assign(v.clone(), initializer),
+            // `x += 1` is just `x = x + 1`
+            (Expr::Var(v), Some(op)) => {
+                let value = Expr::Binary(Box::new(expr.clone()), op, initializer);
+                Expr::Assign(
+                    v.clone(),
+                    Box::new(ExprNode::new(value, line).with_span(span)),
+                )
             }
-            _ => Ok(expr),
-        }
+            (Expr::Index(target, index), None) => {
+                Expr::IndexAssign(target.clone(), index.clone(), initializer)
+            }
+            (Expr::Index(target, index), Some(op)) => {
+                Expr::IndexUpdate(target.clone(), index.clone(), op, initializer)
+            }
+            _ => return Err(ParseError::InvalidAssigment(equals).into()),
+        };
+
+        Ok(ExprNode::new(assign, line).with_span(span))
     }
 
     fn or(&mut self) -> Result<ExprNode> {
@@ -424,10 +813,12 @@ impl Parser {
         while self.match_tok(Kind::Or) {
             let tok = self.previous();
             let right = self.and()?;
+            let span = expr.span().to(right.span());
             expr = ExprNode::new(
                 Expr::Logical(Box::new(expr), LogicalOp::Or, Box::new(right)),
                 tok.line(),
-            );
+            )
+            .with_span(span);
         }
 
         Ok(expr)
@@ -440,10 +831,13 @@ impl Parser {
             let tok = self.previous();
             let right = self.equality()?;
 
+            let span = expr.span().to(right.span());
+
             expr = ExprNode::new(
                 Expr::Logical(Box::new(expr), LogicalOp::And, Box::new(right)),
                 tok.line(),
-            );
+            )
+            .with_span(span);
         }
 
         Ok(expr)
@@ -457,28 +851,40 @@ impl Parser {
             let op: Option<BinaryOp> = self.advance().kind().into();
             let right = self.comparison()?;
 
+            let span = left.span().to(right.span());
+
             left = ExprNode::new(
                 Expr::Binary(Box::new(left), op.unwrap(), Box::new(right)),
                 line,
-            );
+            )
+            .with_span(span);
         }
 
         Ok(left)
     }
 
     fn comparison(&mut self) -> Result<ExprNode> {
-        let mut left = self.term()?;
+        let mut left = self.bit_or()?;
         let line = left.line();
 
         while matches!(
             self.peek().kind(),
             Kind::RightBoomerang | Kind::GTE | Kind::LeftBoomerang | Kind::LTE
         ) {
-            let kind = self.advance().kind();
+            let pos = self.current;
+            let tok = self.advance();
+            let kind = tok.kind();
             let op: Option<BinaryOp> = kind.clone().into();
 
-            let right = match self.term() {
+            let right = match self.bit_or() {
                 Ok(term) => term,
+                Err(e) if is_lexer_error(&e) => return Err(e),
+                Err(_) if self.inside_block > 0 && kind == Kind::RightBoomerang => {
+                    // Most likely the `>` closes the block, so give it back
+                    // for recovery to stop at
+                    self.back(pos);
+                    return Err(ParseError::MissingSemicolon(tok).into());
+                }
                 Err(e) => {
                     if self.inside_block > 0 {
                         return Err(ParseError::Any(
@@ -491,10 +897,90 @@ impl Parser {
                 }
             };
 
+            let span = left.span().to(right.span());
+
             left = ExprNode::new(
                 Expr::Binary(Box::new(left), op.unwrap(), Box::new(right)),
                 line,
-            );
+            )
+            .with_span(span);
+        }
+
+        Ok(left)
+    }
+
+    fn bit_or(&mut self) -> Result<ExprNode> {
+        let mut left = self.bit_xor()?;
+        let line = left.line();
+
+        while self.match_tok(Kind::BitOr) {
+            let right = self.bit_xor()?;
+
+            let span = left.span().to(right.span());
+
+            left = ExprNode::new(
+                Expr::Binary(Box::new(left), BinaryOp::BitOr, Box::new(right)),
+                line,
+            )
+            .with_span(span);
+        }
+
+        Ok(left)
+    }
+
+    fn bit_xor(&mut self) -> Result<ExprNode> {
+        let mut left = self.bit_and()?;
+        let line = left.line();
+
+        while self.match_tok(Kind::BitXor) {
+            let right = self.bit_and()?;
+
+            let span = left.span().to(right.span());
+
+            left = ExprNode::new(
+                Expr::Binary(Box::new(left), BinaryOp::BitXor, Box::new(right)),
+                line,
+            )
+            .with_span(span);
+        }
+
+        Ok(left)
+    }
+
+    fn bit_and(&mut self) -> Result<ExprNode> {
+        let mut left = self.shift()?;
+        let line = left.line();
+
+        while self.match_tok(Kind::BitAnd) {
+            let right = self.shift()?;
+
+            let span = left.span().to(right.span());
+
+            left = ExprNode::new(
+                Expr::Binary(Box::new(left), BinaryOp::BitAnd, Box::new(right)),
+                line,
+            )
+            .with_span(span);
+        }
+
+        Ok(left)
+    }
+
+    fn shift(&mut self) -> Result<ExprNode> {
+        let mut left = self.term()?;
+        let line = left.line();
+
+        while matches!(self.peek().kind(), Kind::ShiftLeft | Kind::ShiftRight) {
+            let op: Option<BinaryOp> = self.advance().kind().into();
+            let right = self.term()?;
+
+            let span = left.span().to(right.span());
+
+            left = ExprNode::new(
+                Expr::Binary(Box::new(left), op.unwrap(), Box::new(right)),
+                line,
+            )
+            .with_span(span);
         }
 
         Ok(left)
@@ -508,10 +994,13 @@ impl Parser {
             let op: Option<BinaryOp> = self.advance().kind().into();
             let right = self.factor()?;
 
+            let span = left.span().to(right.span());
+
             left = ExprNode::new(
                 Expr::Binary(Box::new(left), op.unwrap(), Box::new(right)),
                 line,
-            );
+            )
+            .with_span(span);
         }
 
         Ok(left)
@@ -523,15 +1012,18 @@ impl Parser {
 
         while matches!(
             self.peek().kind(),
-            Kind::Slash | Kind::Asterisk | Kind::Modulo
+            Kind::Slash | Kind::Asterisk | Kind::Modulo | Kind::FloorDivide
         ) {
             let op: Option<BinaryOp> = self.advance().kind().into();
             let right = self.unary()?;
 
+            let span = left.span().to(right.span());
+
             left = ExprNode::new(
                 Expr::Binary(Box::new(left), op.unwrap(), Box::new(right)),
                 line,
-            );
+            )
+            .with_span(span);
         }
 
         Ok(left)
@@ -543,25 +1035,100 @@ impl Parser {
                 let tok = self.advance();
                 let op: Option<UnaryOp> = tok.kind().into();
                 let right = self.unary()?;
-                Ok(ExprNode::new(
-                    Expr::Unary(op.unwrap(), Box::new(right)),
-                    tok.line(),
-                ))
+                let span = tok.span().to(right.span());
+                Ok(
+                    ExprNode::new(Expr::Unary(op.unwrap(), Box::new(right)), tok.line())
+                        .with_span(span),
+                )
             }
-            _ => self.call(),
+            _ => self.power(),
         }
     }
 
+    /// `**` binds tighter than unary operators on its left and is right
+    /// associative, so `-2 ** 2` is -4 and `2 ** 3 ** 2` is 2 ** 9
+    fn power(&mut self) -> Result<ExprNode> {
+        let base = self.call()?;
+        let line = base.line();
+
+        if !self.match_tok(Kind::Power) {
+            return Ok(base);
+        }
+        let exponent = self.unary()?;
+        let span = base.span().to(exponent.span());
+
+        Ok(ExprNode::new(
+            Expr::Binary(Box::new(base), BinaryOp::Power, Box::new(exponent)),
+            line,
+        )
+        .with_span(span))
+    }
+
     fn call(&mut self) -> Result<ExprNode> {
         let mut expr = self.primary()?;
 
-        while self.match_tok(Kind::LeftParen) {
-            expr = self.finish_call(expr)?
+        loop {
+            if self.match_tok(Kind::LeftParen) {
+                expr = self.finish_call(expr)?
+            } else if self.match_tok(Kind::LeftBracket) {
+                expr = self.finish_index(expr)?
+            } else {
+                break;
+            }
         }
 
         Ok(expr)
     }
 
+    fn finish_index(&mut self, target: ExprNode) -> Result<ExprNode> {
+        let index = self.expression()?;
+        let bracket = self.consume(Kind::RightBracket)?;
+        let line = target.line();
+        let span = target.span().to(bracket.span());
+
+        Ok(ExprNode::new(Expr::Index(Box::new(target), Box::new(index)), line).with_span(span))
+    }
+
+    /// Parse the items of a list literal, expects the opening
+    /// bracket has already been consumed
+    fn list(&mut self) -> Result<Expr> {
+        let mut items: Vec<ExprNode> = Vec::new();
+
+        if !self.check(Kind::RightBracket) {
+            loop {
+                items.push(self.expression()?);
+
+                if !self.match_tok(Kind::Comma) || self.check(Kind::RightBracket) {
+                    break;
+                }
+            }
+        }
+        self.consume(Kind::RightBracket)?;
+
+        Ok(Expr::List(items))
+    }
+
+    /// Parse the entries of a map literal, expects the opening
+    /// brace has already been consumed
+    fn map(&mut self) -> Result<Expr> {
+        let mut entries: Vec<(ExprNode, ExprNode)> = Vec::new();
+
+        if !self.check(Kind::RightBrace) {
+            loop {
+                let key = self.expression()?;
+                self.consume(Kind::Colon)?;
+                entries.push((key, self.expression()?));
+
+                if !self.match_tok(Kind::Comma) || self.check(Kind::RightBrace) {
+                    break;
+                }
+            }
+        }
+        self.consume(Kind::RightBrace)?;
+
+        Ok(Expr::Map(entries))
+    }
+
     fn finish_call(&mut self, callee: ExprNode) -> Result<ExprNode> {
         let mut args: Vec<ExprNode> = Vec::new();
 
@@ -580,45 +1147,60 @@ impl Parser {
 
         let paren = self.consume(Kind::RightParen)?;
         let line = paren.line();
+        let span = callee.span().to(paren.span());
 
-        Ok(ExprNode::new(
-            Expr::Call(Box::new(callee), paren, args),
-            line,
-        ))
+        Ok(ExprNode::new(Expr::Call(Box::new(callee), paren, args), line).with_span(span))
     }
 
     fn primary(&mut self) -> Result<ExprNode> {
+        if self.is_at_end() {
+            return Err(ParseError::ExpectPrimary(self.peek()).into());
+        }
+
         let next = self.advance();
         let line = next.line();
 
         let expr = match next.kind() {
             Kind::Number(num) => Expr::Literal(num.into()),
+            Kind::Integer(num) => Expr::Literal(num.into()),
             Kind::String(s) => Expr::Literal(s.into()),
+            Kind::InterpolatedString(parts) => self.interpolation(parts, &next)?,
             Kind::True => Expr::Literal(true.into()),
             Kind::False => Expr::Literal(false.into()),
             Kind::BuggerAll => Expr::Literal(Value::Nil),
-            Kind::Ident(name) => Expr::Var(Var::new(Ident::new(name, line), usize::MAX)),
+            Kind::Ident(name) => Expr::Var(Var::new(
+                Ident::new(name, line).with_span(next.span()),
+                usize::MAX,
+            )),
             Kind::LeftParen => {
                 let expr = self.expression()?;
                 self.consume(Kind::RightParen)?;
                 Expr::Grouping(Box::new(expr))
             }
-            k => {
+            Kind::LeftBracket => self.list()?,
+            Kind::LeftBrace => self.map()?,
+            Kind::YaReckon => self.match_expression()?,
+            Kind::HardYakkaFor => {
+                let name = Ident::new("yakka".into(), line).with_span(next.span());
+                Expr::Lambda(self.function(name)?)
+            }
+            _ => {
                 // self.current -= 1;
                 // panic!("k: {:?}", k);
-                return Err(ParseError::ExpectPrimary(line, k).into());
+                return Err(ParseError::ExpectPrimary(next).into());
             }
         };
 
-        Ok(ExprNode::new(expr, line))
+        Ok(ExprNode::new(expr, line).with_span(next.span().to(self.previous().span())))
     }
 
     fn consume(&mut self, kind: Kind) -> Result<Token> {
+        self.split_shift(&kind);
         if self.check(kind.clone()) {
             return Ok(self.advance());
         }
 
-        Err((ParseError::UnexpectedToken(kind, self.peek().kind(), self.peek().line())).into())
+        Err((ParseError::UnexpectedToken(kind, self.peek())).into())
     }
 
     fn consume_ident(&mut self) -> Result<Ident> {
@@ -626,9 +1208,9 @@ impl Parser {
         match tok.kind() {
             Kind::Ident(name) => {
                 let _ = self.advance();
-                Ok(Ident::new(name, tok.line()))
+                Ok(Ident::new(name, tok.line()).with_span(tok.span()))
             }
-            k => Err(ParseError::UnexpectedToken(Kind::Ident("any".into()), k, tok.line()).into()),
+            _ => Err(ParseError::UnexpectedToken(Kind::Ident("any".into()), tok).into()),
         }
     }
 
@@ -644,6 +1226,7 @@ impl Parser {
     }
 
     fn match_tok(&mut self, kind: Kind) -> bool {
+        self.split_shift(&kind);
         if self.peek().kind() == kind {
             let _ = self.advance();
             true
@@ -652,6 +1235,26 @@ impl Parser {
         }
     }
 
+    /// `<<` and `>>` are lexed as shifts but can also be two boomerangs
+    /// opening or closing nested blocks. If a boomerang is wanted, split the
+    /// shift in two
+    fn split_shift(&mut self, kind: &Kind) {
+        let half = match (kind, self.peek().kind()) {
+            (Kind::LeftBoomerang, Kind::ShiftLeft) | (Kind::RightBoomerang, Kind::ShiftRight) => {
+                kind.clone()
+            }
+            _ => return,
+        };
+        let line = self.peek().line();
+        let Span { start, end } = self.peek().span();
+        let mid = Position::new(start.offset + 1, start.line, start.col + 1);
+        self.tokens[self.current] = Token::new(half.clone(), line).with_span(Span::new(start, mid));
+        self.tokens.insert(
+            self.current + 1,
+            Token::new(half, line).with_span(Span::new(mid, end)),
+        );
+    }
+
     fn check(&self, kind: Kind) -> bool {
         if self.is_at_end() {
             return false;
@@ -691,3 +1294,11 @@ impl Parser {
         self.peek().kind() == Kind::EOF
     }
 }
+
+/// Whether `e` came from running into an error token, which the lexer has
+/// already reported
+fn is_lexer_error(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<ParseError>()
+        .and_then(ParseError::token)
+        .is_some_and(|tok| tok.kind() == Kind::Error)
+}
@@ -0,0 +1,201 @@
+use std::{fs, io::Write, mem, path::Path};
+
+use itertools::Itertools;
+
+use crate::{
+    diagnostic::{Diagnostic, Renderer},
+    lexer::{source, Lexer},
+    parser::parser::Parser,
+    resolver::Resolver,
+    runtime::Interpreter,
+    token::{Kind, Token},
+};
+
+const HELP: &str = "\
+:env            SHOW EVERY VARIABLE AND WHAT IT'S GOT
+:reset          FORGET EVERYTHING AND START AGAIN
+:load <file>    RUN A FILE IN THIS SESSION
+:help           SHOW THIS
+:quit           PISS OFF";
+
+/// What the REPL is waiting for after a line of input
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Status {
+    /// A new entry
+    Ready,
+    /// More lines to finish the current entry
+    Unfinished,
+    /// Nothing, the session is over
+    Quit,
+}
+
+/// An interactive session, fed one line of input at a time. Variables,
+/// functions and imports stick around from one entry to the next
+pub struct Repl<'a> {
+    interpreter: Interpreter<'a>,
+    parser: Parser,
+    resolver: Resolver,
+    // Lines of an entry that isn't finished yet
+    buffer: String,
+    // Whether to colour error messages
+    colour: bool,
+}
+
+impl<'a> Default for Repl<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Repl<'a> {
+    pub fn new() -> Self {
+        Self::with_interpreter(Interpreter::new())
+    }
+
+    pub fn new_with_writer(writer: &'a mut dyn Write) -> Self {
+        Self::with_interpreter(Interpreter::new_with_writer(writer))
+    }
+
+    fn with_interpreter(interpreter: Interpreter<'a>) -> Self {
+        Self {
+            interpreter,
+            parser: Parser::new(vec![]),
+            resolver: Resolver::for_repl(),
+            buffer: String::new(),
+            colour: false,
+        }
+    }
+
+    /// Whether to colour error messages for a terminal
+    pub fn with_colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    /// Whether the lines so far are part way through an entry
+    pub fn is_unfinished(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+
+    /// Throw away the lines of an unfinished entry
+    pub fn cancel(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Feed the REPL a line of input, running it once it makes up a whole
+    /// entry. Lines starting with `:` are meta-commands
+    pub fn feed(&mut self, line: &str) -> Status {
+        if self.buffer.is_empty() {
+            if let Some(cmd) = line.trim().strip_prefix(':') {
+                return self.command(cmd);
+            }
+            if line.trim().is_empty() {
+                return Status::Ready;
+            }
+        }
+
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+
+        // An odd number of `"""` means a raw string is still open
+        if self.buffer.matches("\"\"\"").count() % 2 == 1 {
+            return Status::Unfinished;
+        }
+
+        let mut lex = Lexer::new(source::Regular::new(self.buffer.chars()));
+        let (tokens, diagnostics) = lex.lex();
+        if diagnostics.is_empty() && self.parser.is_unfinished(&tokens) {
+            return Status::Unfinished;
+        }
+
+        let src = mem::take(&mut self.buffer);
+        let quit = tokens.iter().any(|tok| tok.kind() == Kind::Cheers);
+        if let Err(diagnostics) = self.run(tokens, diagnostics) {
+            self.report(&src, &diagnostics);
+        }
+
+        if quit {
+            Status::Quit
+        } else {
+            Status::Ready
+        }
+    }
+
+    fn run(
+        &mut self,
+        tokens: Vec<Token>,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<(), Vec<Diagnostic>> {
+        let mut stmts = crate::parse_tokens(&mut self.parser, tokens, diagnostics)?;
+        self.resolver.resolve(&mut stmts)?;
+        self.interpreter.interpret_echo(stmts)
+    }
+
+    fn command(&mut self, cmd: &str) -> Status {
+        let (name, arg) = match cmd.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (cmd, ""),
+        };
+
+        match name {
+            "env" => self.print_env(),
+            "reset" => self.reset(),
+            "load" if arg.is_empty() => self
+                .interpreter
+                .print_err(format_args!("OI! GIVE US A FILE TO LOAD, YA GALAH")),
+            "load" => self.load(Path::new(arg)),
+            "help" => self.interpreter.print(format_args!("{}", HELP)),
+            "quit" => return Status::Quit,
+            _ => self
+                .interpreter
+                .print_err(format_args!("WHAT'S :{} MEANT TO BE? TRY :help", name)),
+        }
+
+        Status::Ready
+    }
+
+    fn print_env(&mut self) {
+        let values = self.interpreter.env().borrow().clone_values();
+        for (name, val) in values.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+            self.interpreter.print(format_args!("{} = {}", name, val));
+        }
+    }
+
+    fn reset(&mut self) {
+        self.interpreter.reset();
+        self.parser = Parser::new(vec![]);
+        self.resolver = Resolver::for_repl();
+    }
+
+    fn load(&mut self, path: &Path) {
+        let src = match fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(e) => {
+                return self.interpreter.print_err(format_args!(
+                    "STREWTH! COULDN'T READ {}: {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+
+        if let Err(diagnostics) = self.run_file(path, &src) {
+            self.report(&src, &diagnostics);
+        }
+    }
+
+    fn run_file(&mut self, path: &Path, src: &str) -> Result<(), Vec<Diagnostic>> {
+        let mut stmts = crate::parse(source::Regular::new(src.chars()))?;
+        self.resolver.resolve_file(path, &mut stmts)?;
+        self.interpreter.interpret(stmts)
+    }
+
+    /// Print what went wrong with `src`
+    fn report(&mut self, src: &str, diagnostics: &[Diagnostic]) {
+        let renderer = Renderer::new(src).with_colour(self.colour);
+        for diagnostic in diagnostics {
+            self.interpreter
+                .print_err(format_args!("{}", renderer.render(diagnostic)));
+        }
+    }
+}
@@ -1,10 +1,22 @@
-use std::{collections::HashMap, mem, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs, mem,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use anyhow::{anyhow, Result};
+use thiserror::Error;
 
 use crate::{
     ast::{
-        Expr, ExprNode, FnDecl, ForLoop, Ident, If, Match, Pattern, Stmt, UnaryOp, Var as AstVar,
-        VarDecl,
+        Expr, ExprNode, FnDecl, ForEachLoop, ForLoop, Ident, If, Match, MatchBranch, Module,
+        ModuleImport, Pattern, Stmt, TryCatch, UnaryOp, Var as AstVar, VarDecl, WhileLoop,
     },
+    diagnostic::Diagnostic,
+    lexer::source,
+    span::Span,
     token::Token,
 };
 
@@ -21,33 +33,118 @@ enum FunctionKind {
     Function,
 }
 
+enum LoopKind {
+    None,
+    Loop,
+}
+
+/// Modules shared by every resolver in a program, so each file is
+/// only loaded once
+#[derive(Default)]
+struct Modules {
+    loaded: HashMap<PathBuf, Rc<Module>>,
+    // Files part way through being loaded, to catch circular imports
+    loading: Vec<PathBuf>,
+}
+
+#[derive(Clone)]
 struct Var {
     // To prevent reading a variable in its initializer
     in_initializer: bool,
     immutable: bool,
+    // Where it was declared, if that's in the source being resolved
+    decl: Option<Span>,
+    // The module it was imported from, so importing it again changes nothing
+    module: Option<PathBuf>,
 }
 
 pub struct Resolver {
     scopes: Vec<HashMap<Rc<str>, Var>>,
-    had_error: bool,
+    diagnostics: Vec<Diagnostic>,
     cur_fn: FunctionKind,
+    cur_loop: LoopKind,
+    // Labels of the loops we're currently inside
+    loop_labels: Vec<Rc<str>>,
+    // Directory that imports are relative to
+    dir: PathBuf,
+    modules: Rc<RefCell<Modules>>,
+    // Whether globals may be redeclared, for the REPL
+    repl: bool,
 }
 
 impl Resolver {
     pub fn new() -> Self {
         Self {
             scopes: vec![HashMap::new()],
-            had_error: false,
+            diagnostics: Vec::new(),
             cur_fn: FunctionKind::None,
+            cur_loop: LoopKind::None,
+            loop_labels: Vec::new(),
+            dir: PathBuf::new(),
+            modules: Rc::new(RefCell::new(Modules::default())),
+            repl: false,
+        }
+    }
+
+    /// Create a resolver to be reused across REPL lines, which lets
+    /// variables that aren't constants be redeclared at the top level
+    pub fn for_repl() -> Self {
+        Self {
+            repl: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a resolver for the file at `path`, which imports are relative to
+    pub fn for_file(path: &Path) -> Self {
+        Self::new().with_file(path)
+    }
+
+    fn with_file(mut self, path: &Path) -> Self {
+        if let Some(dir) = path.parent() {
+            self.dir = dir.to_path_buf();
         }
+        if let Ok(path) = fs::canonicalize(path) {
+            self.modules.borrow_mut().loading.push(path);
+        }
+        self
+    }
+
+    /// Resolve `stmts`, handing back anything wrong with them. The globals
+    /// are rolled back on error, since the statements will never run
+    pub fn resolve(&mut self, stmts: &mut [Stmt]) -> std::result::Result<(), Vec<Diagnostic>> {
+        // Globals from earlier sources were declared somewhere we can't point to
+        self.scopes[0].values_mut().for_each(|var| var.decl = None);
+        let globals = self.scopes[0].clone();
+        self.resolve_stmts(stmts);
+
+        let diagnostics = mem::take(&mut self.diagnostics);
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        self.scopes.truncate(1);
+        self.scopes[0] = globals;
+        Err(diagnostics)
     }
 
-    pub fn resolve(&mut self, stmts: &mut [Stmt]) -> bool {
+    /// Resolve `stmts` from the file at `path`, with imports relative to it
+    pub fn resolve_file(
+        &mut self,
+        path: &Path,
+        stmts: &mut [Stmt],
+    ) -> std::result::Result<(), Vec<Diagnostic>> {
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let dir = mem::replace(&mut self.dir, dir);
+        let result = self.resolve(stmts);
+        self.dir = dir;
+        result
+    }
+
+    fn resolve_stmts(&mut self, stmts: &mut [Stmt]) {
         for stmt in stmts {
             self.stmt(stmt);
         }
-
-        self.had_error
     }
 
     fn stmt(&mut self, stmt: &mut Stmt) {
@@ -62,11 +159,16 @@ impl Resolver {
             Stmt::If(If { cond, then, else_ }) => self.if_stmt(cond, then, else_),
             Stmt::Print(expr) => self.print_stmt(expr),
             Stmt::Return(tok, expr) => self.ret_stmt(tok, expr),
-            Stmt::While(node) => self.while_stmt(&mut node.cond, &mut node.body),
+            Stmt::Try(try_catch) => self.try_stmt(try_catch),
+            Stmt::Throw(_, expr) => self.expr(expr.expr_mut()),
+            Stmt::While(while_loop) => self.while_stmt(while_loop),
             Stmt::For(for_loop) => self.for_stmt(for_loop),
+            Stmt::ForEach(for_each) => self.for_each_stmt(for_each),
             Stmt::Match(match_) => self.match_stmt(match_),
             Stmt::Import(ident) => self.import_stmt(ident),
-            Stmt::Break(_) => {}
+            Stmt::ImportModule(import) => self.import_module_stmt(import),
+            Stmt::Break(_, label) => self.break_stmt(label),
+            Stmt::Continue(tok, label) => self.continue_stmt(tok, label),
             Stmt::Exit(_) => {}
             Stmt::Expr(expr) => self.expr(expr.expr_mut()),
         }
@@ -74,7 +176,7 @@ impl Resolver {
 
     fn block_stmt(&mut self, stmts: &mut [Stmt]) {
         self.begin_scope();
-        self.resolve(stmts);
+        self.resolve_stmts(stmts);
         self.end_scope();
     }
 
@@ -108,10 +210,9 @@ impl Resolver {
 
     fn ret_stmt(&mut self, tok: &mut Token, expr: &mut Option<ExprNode>) {
         if let FunctionKind::None = self.cur_fn {
-            self.print_error(
-                tok.line(),
-                &tok.kind.to_string(),
-                "YA CAN ONLY RETURN IN FUNCTIONS DUMMY!",
+            self.error(
+                ResolveError::ReturnOutsideFunction(tok.line(), tok.kind.to_string()),
+                tok.span(),
             );
         }
         if let Some(expr) = expr {
@@ -119,15 +220,80 @@ impl Resolver {
         }
     }
 
-    fn while_stmt(&mut self, cond: &mut ExprNode, body: &mut Vec<Stmt>) {
-        self.expr(cond.expr_mut());
-        body.iter_mut().for_each(|stmt| self.stmt(stmt));
+    fn try_stmt(&mut self, try_catch: &mut TryCatch) {
+        try_catch.body.iter_mut().for_each(|stmt| self.stmt(stmt));
+
+        with_scope!(self, {
+            self.declare(&try_catch.var, false);
+            self.define(&try_catch.var);
+
+            try_catch.handler.iter_mut().for_each(|stmt| {
+                self.stmt(stmt);
+            });
+        });
+    }
+
+    fn break_stmt(&mut self, label: &Option<Ident>) {
+        self.loop_label(label);
+    }
+
+    fn continue_stmt(&mut self, tok: &Token, label: &Option<Ident>) {
+        if let LoopKind::None = self.cur_loop {
+            self.error(
+                ResolveError::ContinueOutsideLoop(tok.line(), tok.kind.to_string()),
+                tok.span(),
+            );
+        }
+        self.loop_label(label);
+    }
+
+    fn loop_label(&mut self, label: &Option<Ident>) {
+        if let Some(label) = label {
+            if !self.loop_labels.contains(&label.name) {
+                self.error(
+                    ResolveError::UnknownLabel(label.line(), label.name.to_string()),
+                    label.span(),
+                );
+            }
+        }
+    }
+
+    fn begin_loop(&mut self, label: &Option<Ident>) -> LoopKind {
+        if let Some(label) = label {
+            if self.loop_labels.contains(&label.name) {
+                self.error(
+                    ResolveError::DuplicateLabel(label.line(), label.name.to_string()),
+                    label.span(),
+                );
+            }
+            self.loop_labels.push(label.name.clone());
+        }
+        mem::replace(&mut self.cur_loop, LoopKind::Loop)
+    }
+
+    fn end_loop(&mut self, label: &Option<Ident>, enclosing_loop: LoopKind) {
+        if label.is_some() {
+            self.loop_labels.pop();
+        }
+        self.cur_loop = enclosing_loop;
+    }
+
+    fn while_stmt(&mut self, while_loop: &mut WhileLoop) {
+        self.expr(while_loop.cond.expr_mut());
+
+        let enclosing_loop = self.begin_loop(&while_loop.label);
+        while_loop.body.iter_mut().for_each(|stmt| self.stmt(stmt));
+        self.end_loop(&while_loop.label, enclosing_loop);
     }
 
     fn for_stmt(&mut self, for_loop: &mut ForLoop) {
         self.expr(for_loop.range.0.expr_mut().expr_mut());
         self.expr(for_loop.range.1.expr_mut().expr_mut());
+        if let Some(step) = &mut for_loop.step {
+            self.expr(step.expr_mut());
+        }
 
+        let enclosing_loop = self.begin_loop(&for_loop.label);
         with_scope!(self, {
             self.declare(&for_loop.var.ident, false);
             self.define(&for_loop.var.ident());
@@ -136,56 +302,169 @@ impl Resolver {
                 self.stmt(stmt);
             });
         });
+        self.end_loop(&for_loop.label, enclosing_loop);
+    }
+
+    fn for_each_stmt(&mut self, for_each: &mut ForEachLoop) {
+        self.expr(for_each.iterable.expr_mut());
+
+        let enclosing_loop = self.begin_loop(&for_each.label);
+        with_scope!(self, {
+            self.declare(&for_each.var.ident, false);
+            self.define(&for_each.var.ident());
+
+            for_each.body.iter_mut().for_each(|stmt| {
+                self.stmt(stmt);
+            });
+        });
+        self.end_loop(&for_each.label, enclosing_loop);
     }
 
     fn match_stmt(&mut self, match_: &mut Match) {
         self.expr(match_.val.expr_mut());
 
         match_.branches.iter_mut().for_each(|branch| {
-            self.block_stmt(&mut branch.body);
+            self.match_branch(branch, |resolver, body| {
+                body.iter_mut().for_each(|stmt| resolver.stmt(stmt))
+            });
         });
 
         if let Some(default) = &mut match_.default {
-            if let Pattern::Var(var) = &mut default.pat {
-                with_scope!(self, {
-                    self.declare(&var.ident, false);
-                    self.define(&var.ident());
-                    default.body.iter_mut().for_each(|stmt| {
-                        self.stmt(stmt);
-                    });
-                });
-            }
+            self.match_branch(default, |resolver, body| {
+                body.iter_mut().for_each(|stmt| resolver.stmt(stmt))
+            });
         }
     }
 
+    /// Resolve a match branch, using `body` to resolve what comes after its `~`
+    fn match_branch<T>(&mut self, branch: &mut MatchBranch<T>, body: fn(&mut Self, &mut T)) {
+        with_scope!(self, {
+            if let Pattern::Var(var) = &branch.pat {
+                self.declare(&var.ident, false);
+                self.define(&var.ident());
+            }
+            if let Some(guard) = &mut branch.guard {
+                self.expr(guard.expr_mut());
+            }
+            body(self, &mut branch.body);
+        });
+    }
+
     fn import_stmt(&mut self, name: &Ident) {
         self.declare(name, true);
         self.define(name);
     }
 
+    fn import_module_stmt(&mut self, import: &mut ModuleImport) {
+        let module = match self.load_module(&import.path) {
+            Ok(module) => module,
+            Err(e) => {
+                return self.error(
+                    ResolveError::Import(import.line, import.path.to_string(), e.to_string()),
+                    import.span,
+                )
+            }
+        };
+
+        for export in &module.exports {
+            let imported = self
+                .scopes
+                .last()
+                .and_then(|scope| scope.get(&export.name))
+                .is_some_and(|var| var.module.as_ref() == Some(&module.path));
+            if imported {
+                continue;
+            }
+
+            // Exports were declared in another file, so point at the import
+            let ident = Ident::new(export.name.to_string(), import.line).with_span(import.span);
+            self.declare_from(&ident, true, Some(import.span));
+            self.define(&ident);
+            if let Some(var) = self
+                .scopes
+                .last_mut()
+                .and_then(|scope| scope.get_mut(&export.name))
+            {
+                var.module = Some(module.path.clone());
+            }
+        }
+        import.module = Some(module);
+    }
+
+    fn load_module(&mut self, path: &str) -> Result<Rc<Module>> {
+        let path = fs::canonicalize(self.dir.join(path))
+            .map_err(|_| anyhow!("CAN'T FIND THAT FILE, YA DRONGO!"))?;
+
+        if let Some(module) = self.modules.borrow().loaded.get(&path) {
+            return Ok(module.clone());
+        }
+        if self.modules.borrow().loading.contains(&path) {
+            return Err(anyhow!(
+                "STREWTH! THESE FILES IMPORT EACH OTHER AROUND IN CIRCLES"
+            ));
+        }
+
+        let src =
+            fs::read_to_string(&path).map_err(|e| anyhow!("COULDN'T READ THAT FILE: {}", e))?;
+        let mut stmts = match crate::parse(source::Regular::new(src.chars())) {
+            Ok(stmts) => stmts,
+            Err(diagnostics) => return Err(self.module_errors(&path, diagnostics)),
+        };
+
+        // Each module gets its own resolver, sharing the loaded modules
+        let mut resolver = Resolver {
+            modules: self.modules.clone(),
+            ..Resolver::new()
+        }
+        .with_file(&path);
+        let result = resolver.resolve(&mut stmts);
+        self.modules.borrow_mut().loading.pop();
+        if let Err(diagnostics) = result {
+            return Err(self.module_errors(&path, diagnostics));
+        }
+
+        let module = Rc::new(Module::new(path.clone(), stmts));
+        self.modules
+            .borrow_mut()
+            .loaded
+            .insert(path, module.clone());
+        Ok(module)
+    }
+
     fn declare(&mut self, ident: &Ident, immutable: bool) {
-        let mut exists = false;
+        self.declare_from(ident, immutable, Some(ident.span()))
+    }
+
+    fn declare_from(&mut self, ident: &Ident, immutable: bool, decl: Option<Span>) {
+        // Where the existing one was declared, if there is one that clashes
+        let mut exists = None;
         let name = &ident.name;
+        let redeclarable = self.repl && self.scopes.len() == 1;
 
         if let Some(scope) = self.scopes.last_mut() {
-            if scope.contains_key(name) {
-                exists = true;
+            if let Some(var) = scope.get(name) {
+                if !redeclarable || var.immutable {
+                    exists = Some(var.decl);
+                }
             }
             scope.insert(
                 name.clone(),
                 Var {
                     in_initializer: false,
                     immutable,
+                    decl,
+                    module: None,
                 },
             );
         }
 
-        if exists {
-            self.print_error(
-                ident.line(),
-                name,
-                "WAKE UP FUCK-WIT! A VARIABLE WITH THAT NAME ALREADY EXISTS IN THIS SCOPE.",
-            )
+        if let Some(prev) = exists {
+            let e = ResolveError::AlreadyDeclared(ident.line(), name.to_string());
+            let diagnostic = Diagnostic::from(&e).with_span(ident.span());
+            self.diagnostics.push(match prev {
+                Some(prev) => diagnostic.with_label(prev, "FIRST DECLARED HERE"),
+                None => diagnostic,
+            });
         }
     }
 
@@ -194,7 +473,8 @@ impl Resolver {
             if let Some(v) = scope.get_mut(&name.name) {
                 v.in_initializer = true;
             } else {
-                self.print_error(name.line(), &name.name, "CAN'T DEFINE AN UNDECLARED VAR")
+                let e = ResolveError::Undeclared(name.line(), name.name.to_string());
+                self.error(e, name.span())
             }
         }
     }
@@ -215,24 +495,18 @@ impl Resolver {
             }
         }
 
-        // Bug in borrow checker won't allow the code below to compile so just paste it in here for now
-        self.had_error = true;
-        eprintln!(
-            "[line {}] {}: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!",
-            var.line(),
-            var.name()
-        );
-        // self.print_error(
-        //     var.line(),
-        //     var.name(),
-        //     "CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!",
-        // );
+        // Bug in borrow checker won't allow calling `self.error` here, so push it directly
+        let e = ResolveError::Undefined(var.line(), var.name().to_string());
+        self.diagnostics
+            .push(Diagnostic::from(&e).with_span(var.span()));
 
         None
     }
 
     fn resolve_fn(&mut self, decl: &mut FnDecl, kind: FunctionKind) {
         let enclosing_fn = mem::replace(&mut self.cur_fn, kind);
+        let enclosing_loop = mem::replace(&mut self.cur_loop, LoopKind::None);
+        let enclosing_labels = mem::take(&mut self.loop_labels);
 
         self.begin_scope();
         decl.params.iter().for_each(|param| {
@@ -243,6 +517,8 @@ impl Resolver {
         self.end_scope();
 
         self.cur_fn = enclosing_fn;
+        self.cur_loop = enclosing_loop;
+        self.loop_labels = enclosing_labels;
     }
 }
 
@@ -266,6 +542,35 @@ impl Resolver {
                 args.iter_mut().for_each(|arg| self.expr(arg.expr_mut()));
             }
             Expr::Grouping(expr) => self.expr(expr.expr_mut()),
+            Expr::Interpolation(parts) => {
+                parts.iter_mut().for_each(|part| self.expr(part.expr_mut()))
+            }
+            Expr::List(items) => items.iter_mut().for_each(|item| self.expr(item.expr_mut())),
+            Expr::Map(entries) => entries.iter_mut().for_each(|(key, value)| {
+                self.expr(key.expr_mut());
+                self.expr(value.expr_mut());
+            }),
+            Expr::Index(target, index) => {
+                self.expr(target.expr_mut());
+                self.expr(index.expr_mut());
+            }
+            Expr::IndexAssign(target, index, value) => {
+                self.expr(value.expr_mut());
+                self.expr(target.expr_mut());
+                self.expr(index.expr_mut());
+            }
+            Expr::IndexUpdate(target, index, _, value) => {
+                self.expr(target.expr_mut());
+                self.expr(index.expr_mut());
+                self.expr(value.expr_mut());
+            }
+            Expr::Lambda(decl) => self.resolve_fn(decl, FunctionKind::Function),
+            Expr::Match(match_) => {
+                self.expr(match_.val.expr_mut());
+                for branch in match_.branches.iter_mut().chain(&mut match_.default) {
+                    self.match_branch(branch, |resolver, body| resolver.expr(body.expr_mut()));
+                }
+            }
             Expr::Literal(_) => {}
             Expr::Logical(left, _, right) => {
                 self.expr(left.expr_mut());
@@ -280,7 +585,9 @@ impl Resolver {
         if let Expr::Var(v) = expr.expr_mut() {
             if let Some(var) = self.resolve_local(v) {
                 if var.immutable {
-                    self.print_error(v.line(), v.name(), "HEY DRONGO, YA CAN'T CHANGE THAT VAR!")
+                    let decl = var.decl;
+                    let e = ResolveError::ChangeConstant(v.line(), v.name().to_string());
+                    self.constant_error(e, v.span(), decl)
                 }
             }
         } else {
@@ -296,11 +603,10 @@ impl Resolver {
                     in_initializer: initialized,
                     ..
                 }) if !initialized => {
-                    return self.print_error(
-                    var.line(),
-                        name,
-                        "FUCK ME DEAD MATE... YOU JUST TRIED TO READ A VARIABLE IN ITS INITIALIZER!",
-                );
+                    return self.error(
+                        ResolveError::ReadInInitializer(var.line(), name.to_string()),
+                        var.span(),
+                    );
                 }
                 _ => {}
             };
@@ -313,15 +619,101 @@ impl Resolver {
         self.expr(init.expr_mut());
         if let Some(v) = self.resolve_local(var) {
             if v.immutable {
-                self.print_error(var.line(), var.name(), "OI, YA CAN'T REDEFINE THIS!")
+                let decl = v.decl;
+                let e = ResolveError::RedefineConstant(var.line(), var.name().to_string());
+                self.constant_error(e, var.span(), decl)
             }
         }
     }
 }
 
 impl Resolver {
-    fn print_error(&mut self, line: usize, name: &str, msg: &str) {
-        self.had_error = true;
-        eprintln!("[line {}] {}: {}", line, name, msg)
+    fn error(&mut self, e: ResolveError, span: Span) {
+        self.diagnostics.push(Diagnostic::from(&e).with_span(span));
+    }
+
+    /// Report a constant being changed, pointing out where it was declared
+    fn constant_error(&mut self, e: ResolveError, span: Span, decl: Option<Span>) {
+        let diagnostic = Diagnostic::from(&e).with_span(span);
+        self.diagnostics.push(match decl {
+            Some(decl) => diagnostic.with_label(decl, "CONSTANT DECLARED HERE"),
+            None => diagnostic,
+        });
+    }
+
+    /// Pass on the problems with the module at `path`, noting where they're from
+    fn module_errors(&mut self, path: &Path, diagnostics: Vec<Diagnostic>) -> anyhow::Error {
+        // Where they point is in that file, not the one being resolved
+        self.diagnostics
+            .extend(diagnostics.into_iter().map(|mut d| {
+                let note = match d.line.take() {
+                    Some(line) => format!("ON LINE {} OF {}", line, path.display()),
+                    None => format!("IN {}", path.display()),
+                };
+                d.span = None;
+                d.labels.clear();
+                d.with_note(note)
+            }));
+        anyhow!("THAT FILE'S GOT ERRORS, SORT IT OUT FIRST")
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ResolveError {
+    #[error("{1}: YA CAN ONLY RETURN IN FUNCTIONS DUMMY!")]
+    ReturnOutsideFunction(usize, String),
+    #[error("{1}: YA CAN ONLY SAY SHE'LL BE RIGHT IN A WALKABOUT DUMMY!")]
+    ContinueOutsideLoop(usize, String),
+    #[error("{1}: THERE'S NO WALKABOUT BY THAT NAME AROUND HERE, YA GALAH!")]
+    UnknownLabel(usize, String),
+    #[error("{1}: STREWTH! YA ALREADY INSIDE A WALKABOUT WITH THAT NAME.")]
+    DuplicateLabel(usize, String),
+    #[error("{1}: {2}")]
+    Import(usize, String, String),
+    #[error("{1}: WAKE UP FUCK-WIT! A VARIABLE WITH THAT NAME ALREADY EXISTS IN THIS SCOPE.")]
+    AlreadyDeclared(usize, String),
+    #[error("{1}: CAN'T DEFINE AN UNDECLARED VAR")]
+    Undeclared(usize, String),
+    #[error("{1}: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!")]
+    Undefined(usize, String),
+    #[error("{1}: FUCK ME DEAD MATE... YOU JUST TRIED TO READ A VARIABLE IN ITS INITIALIZER!")]
+    ReadInInitializer(usize, String),
+    #[error("{1}: HEY DRONGO, YA CAN'T CHANGE THAT VAR!")]
+    ChangeConstant(usize, String),
+    #[error("{1}: OI, YA CAN'T REDEFINE THIS!")]
+    RedefineConstant(usize, String),
+}
+
+impl ResolveError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ReturnOutsideFunction(..) => "R001",
+            Self::ContinueOutsideLoop(..) => "R002",
+            Self::UnknownLabel(..) => "R003",
+            Self::DuplicateLabel(..) => "R004",
+            Self::Import(..) => "R005",
+            Self::AlreadyDeclared(..) => "R006",
+            Self::Undeclared(..) => "R007",
+            Self::Undefined(..) => "R008",
+            Self::ReadInInitializer(..) => "R009",
+            Self::ChangeConstant(..) => "R010",
+            Self::RedefineConstant(..) => "R011",
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Self::ReturnOutsideFunction(line, _)
+            | Self::ContinueOutsideLoop(line, _)
+            | Self::UnknownLabel(line, _)
+            | Self::DuplicateLabel(line, _)
+            | Self::Import(line, _, _)
+            | Self::AlreadyDeclared(line, _)
+            | Self::Undeclared(line, _)
+            | Self::Undefined(line, _)
+            | Self::ReadInInitializer(line, _)
+            | Self::ChangeConstant(line, _)
+            | Self::RedefineConstant(line, _) => *line,
+        }
     }
 }
@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::rc::Rc;
 use std::{thread, time::Duration};
@@ -12,7 +13,7 @@ use rand::prelude::ThreadRng;
 use rand::Rng;
 
 use crate::runtime::error::RuntimeError;
-use crate::runtime::{Interpreter, Value};
+use crate::runtime::{Interpreter, Key, Value};
 
 use super::AussieCallable;
 
@@ -27,6 +28,10 @@ pub enum BuiltIn {
     Sleep(Sleep),
     Time(Time),
     Rand(Rand),
+    Len(Len),
+    Has(Has),
+    Remove(Remove),
+    Keys(Keys),
 }
 
 impl BuiltIn {
@@ -35,17 +40,30 @@ impl BuiltIn {
             "HitTheSack" => Some(BuiltIn::Sleep(Sleep::default())),
             "GimmeTime" => Some(BuiltIn::Time(Time::default())),
             "ChuckSomeDice" => Some(BuiltIn::Rand(Rand::default())),
+            "HowLongIsAPieceOfString" => Some(BuiltIn::Len(Len::default())),
+            "GotAny" => Some(BuiltIn::Has(Has::default())),
+            "BinIt" => Some(BuiltIn::Remove(Remove::default())),
+            "GimmeKeys" => Some(BuiltIn::Keys(Keys::default())),
             _ => None,
         }
     }
 }
 
 impl AussieCallable for BuiltIn {
-    fn call(&self, interpreter: &mut Interpreter, args: &[Value]) -> anyhow::Result<Value> {
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        args: &[Value],
+        line: usize,
+    ) -> anyhow::Result<Value> {
         match self {
-            Self::Sleep(sleep) => sleep.call(interpreter, args),
-            Self::Time(time) => time.call(interpreter, args),
-            Self::Rand(rand) => rand.call(interpreter, args),
+            Self::Sleep(sleep) => sleep.call(interpreter, args, line),
+            Self::Time(time) => time.call(interpreter, args, line),
+            Self::Rand(rand) => rand.call(interpreter, args, line),
+            Self::Len(len) => len.call(interpreter, args, line),
+            Self::Has(has) => has.call(interpreter, args, line),
+            Self::Remove(remove) => remove.call(interpreter, args, line),
+            Self::Keys(keys) => keys.call(interpreter, args, line),
         }
     }
 
@@ -54,6 +72,10 @@ impl AussieCallable for BuiltIn {
             Self::Sleep(sleep) => sleep.arity(),
             Self::Time(time) => time.arity(),
             Self::Rand(rand) => rand.arity(),
+            Self::Len(len) => len.arity(),
+            Self::Has(has) => has.arity(),
+            Self::Remove(remove) => remove.arity(),
+            Self::Keys(keys) => keys.arity(),
         }
     }
 
@@ -62,6 +84,10 @@ impl AussieCallable for BuiltIn {
             Self::Sleep(sleep) => sleep.name(),
             Self::Time(time) => time.name(),
             Self::Rand(rand) => rand.name(),
+            Self::Len(len) => len.name(),
+            Self::Has(has) => has.name(),
+            Self::Remove(remove) => remove.name(),
+            Self::Keys(keys) => keys.name(),
         }
     }
 }
@@ -72,6 +98,10 @@ impl Display for BuiltIn {
             Self::Sleep(s) => write!(f, "{}(ms)", s.name()),
             Self::Time(t) => write!(f, "{}()", t.name()),
             Self::Rand(r) => write!(f, "{}(start, end)", r.name()),
+            Self::Len(l) => write!(f, "{}(thing)", l.name()),
+            Self::Has(h) => write!(f, "{}(map, key)", h.name()),
+            Self::Remove(r) => write!(f, "{}(map, key)", r.name()),
+            Self::Keys(k) => write!(f, "{}(map)", k.name()),
         }
     }
 }
@@ -90,10 +120,10 @@ impl Default for Sleep {
 }
 
 impl AussieCallable for Sleep {
-    fn call(&self, _: &mut Interpreter, args: &[Value]) -> anyhow::Result<Value> {
-        let duration = match &args[0] {
-            Value::Number(n) => *n,
-            _ => return Err(RuntimeError::General("expected a number".into()).into()),
+    fn call(&self, _: &mut Interpreter, args: &[Value], _: usize) -> anyhow::Result<Value> {
+        let duration = match args[0].as_f64() {
+            Some(n) => n,
+            None => return Err(RuntimeError::General("expected a number".into()).into()),
         };
 
         if duration < 0.0 {
@@ -129,7 +159,7 @@ impl Default for Time {
 
 impl AussieCallable for Time {
     #[cfg(not(target_os = "emscripten"))]
-    fn call(&self, _: &mut Interpreter, _: &[Value]) -> anyhow::Result<Value> {
+    fn call(&self, _: &mut Interpreter, _: &[Value], _: usize) -> anyhow::Result<Value> {
         let utc = Utc::now().naive_utc();
         let tz = chrono_tz::Australia::Melbourne.from_utc_datetime(&utc);
 
@@ -137,7 +167,7 @@ impl AussieCallable for Time {
     }
 
     #[cfg(target_os = "emscripten")]
-    fn call(&self, _: &mut Interpreter, _: &[Value]) -> anyhow::Result<Value> {
+    fn call(&self, _: &mut Interpreter, _: &[Value], _: usize) -> anyhow::Result<Value> {
         use std::ffi::CString;
 
         let str = unsafe {
@@ -181,14 +211,21 @@ impl Default for Rand {
 }
 
 impl AussieCallable for Rand {
-    fn call(&self, _: &mut Interpreter, args: &[Value]) -> anyhow::Result<Value> {
-        let (start, end) = match (&args[0], &args[1]) {
-            (Value::Number(a), Value::Number(b)) => (*a as i64, *b as i64),
+    fn call(&self, _: &mut Interpreter, args: &[Value], line: usize) -> anyhow::Result<Value> {
+        let (start, end) = match (args[0].as_i64(), args[1].as_i64()) {
+            (Some(start), Some(end)) => (start, end),
             _ => {
-                return Err(RuntimeError::General(
-                    "OI MATE, CAN YA FUCKIN' COUNT?? EXPECTED A NUMBER".into(),
+                let bad = if args[0].as_i64().is_none() {
+                    &args[0]
+                } else {
+                    &args[1]
+                };
+                return Err(RuntimeError::NotAnInteger(
+                    line,
+                    self.name.to_string(),
+                    bad.to_string(),
                 )
-                .into())
+                .into());
             }
         };
 
@@ -198,10 +235,12 @@ impl AussieCallable for Rand {
             )
             .into());
         }
+        // There's nothing between them to pick from
+        if start == end {
+            return Ok(Value::Integer(start));
+        }
 
-        Ok(Value::Number(
-            self.rng.borrow_mut().gen_range(start..end) as f64
-        ))
+        Ok(Value::Integer(self.rng.borrow_mut().gen_range(start..end)))
     }
 
     fn arity(&self) -> u8 {
@@ -212,3 +251,140 @@ impl AussieCallable for Rand {
         &self.name
     }
 }
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Len {
+    name: Rc<str>,
+}
+
+impl Default for Len {
+    fn default() -> Self {
+        Self {
+            name: Rc::from("HowLongIsAPieceOfString"),
+        }
+    }
+}
+
+impl AussieCallable for Len {
+    fn call(&self, _: &mut Interpreter, args: &[Value], line: usize) -> anyhow::Result<Value> {
+        let len = match &args[0] {
+            Value::List(list) => list.borrow().len(),
+            Value::String(s) => s.chars().count(),
+            Value::Map(map) => map.borrow().len(),
+            other => return Err(RuntimeError::NoLength(line, other.type_name()).into()),
+        };
+
+        Ok(Value::Integer(len as i64))
+    }
+
+    fn arity(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &Rc<str> {
+        &self.name
+    }
+}
+
+fn unwrap_map(val: &Value, line: usize) -> anyhow::Result<Rc<RefCell<BTreeMap<Key, Value>>>> {
+    match val {
+        Value::Map(map) => Ok(map.clone()),
+        other => Err(RuntimeError::NotAMap(line, other.type_name()).into()),
+    }
+}
+
+fn unwrap_key(val: &Value, line: usize) -> anyhow::Result<Key> {
+    Key::from_value(val).ok_or_else(|| RuntimeError::InvalidKey(line, val.type_name()).into())
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Has {
+    name: Rc<str>,
+}
+
+impl Default for Has {
+    fn default() -> Self {
+        Self {
+            name: Rc::from("GotAny"),
+        }
+    }
+}
+
+impl AussieCallable for Has {
+    fn call(&self, _: &mut Interpreter, args: &[Value], line: usize) -> anyhow::Result<Value> {
+        let map = unwrap_map(&args[0], line)?;
+        let key = unwrap_key(&args[1], line)?;
+
+        let has = map.borrow().contains_key(&key);
+        Ok(Value::Bool(has))
+    }
+
+    fn arity(&self) -> u8 {
+        2
+    }
+
+    fn name(&self) -> &Rc<str> {
+        &self.name
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Remove {
+    name: Rc<str>,
+}
+
+impl Default for Remove {
+    fn default() -> Self {
+        Self {
+            name: Rc::from("BinIt"),
+        }
+    }
+}
+
+impl AussieCallable for Remove {
+    fn call(&self, _: &mut Interpreter, args: &[Value], line: usize) -> anyhow::Result<Value> {
+        let map = unwrap_map(&args[0], line)?;
+        let key = unwrap_key(&args[1], line)?;
+
+        let removed = map.borrow_mut().remove(&key);
+        Ok(removed.unwrap_or(Value::Nil))
+    }
+
+    fn arity(&self) -> u8 {
+        2
+    }
+
+    fn name(&self) -> &Rc<str> {
+        &self.name
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Keys {
+    name: Rc<str>,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self {
+            name: Rc::from("GimmeKeys"),
+        }
+    }
+}
+
+impl AussieCallable for Keys {
+    fn call(&self, _: &mut Interpreter, args: &[Value], line: usize) -> anyhow::Result<Value> {
+        let map = unwrap_map(&args[0], line)?;
+
+        let keys: Vec<Value> = map.borrow().keys().cloned().map(Value::from).collect();
+        Ok(keys.into())
+    }
+
+    fn arity(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &Rc<str> {
+        &self.name
+    }
+}
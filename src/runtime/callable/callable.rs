@@ -7,7 +7,7 @@ use crate::runtime::{Interpreter, Value};
 use super::{BuiltIn, Function, UserDefined};
 
 pub trait AussieCallable {
-    fn call(&self, interpreter: &mut Interpreter, args: &[Value]) -> Result<Value>;
+    fn call(&self, interpreter: &mut Interpreter, args: &[Value], line: usize) -> Result<Value>;
     fn arity(&self) -> u8;
     fn name(&self) -> &Rc<str>;
 }
@@ -18,9 +18,9 @@ pub enum Callable {
 }
 
 impl AussieCallable for Callable {
-    fn call(&self, interpreter: &mut Interpreter, args: &[Value]) -> Result<Value> {
+    fn call(&self, interpreter: &mut Interpreter, args: &[Value], line: usize) -> Result<Value> {
         match self {
-            Callable::Function(func) => func.call(interpreter, args),
+            Callable::Function(func) => func.call(interpreter, args, line),
         }
     }
 
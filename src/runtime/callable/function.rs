@@ -16,10 +16,10 @@ pub enum Function {
 }
 
 impl AussieCallable for Function {
-    fn call(&self, interpreter: &mut Interpreter, args: &[Value]) -> Result<Value> {
+    fn call(&self, interpreter: &mut Interpreter, args: &[Value], line: usize) -> Result<Value> {
         match self {
-            Function::UserDefined(func) => func.call(interpreter, args),
-            Function::BuiltIn(built_in) => built_in.call(interpreter, args),
+            Function::UserDefined(func) => func.call(interpreter, args, line),
+            Function::BuiltIn(built_in) => built_in.call(interpreter, args, line),
         }
     }
 
@@ -61,7 +61,7 @@ impl UserDefined {
 }
 
 impl AussieCallable for UserDefined {
-    fn call(&self, interpreter: &mut Interpreter, args: &[Value]) -> Result<Value> {
+    fn call(&self, interpreter: &mut Interpreter, args: &[Value], _: usize) -> Result<Value> {
         let mut env = Environment::new_with_enclosing(self.env.clone());
 
         for (parameter, value) in self.decl.params.iter().zip(args.iter()) {
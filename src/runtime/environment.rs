@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     rc::Rc,
 };
 
@@ -11,6 +11,13 @@ use super::Value;
 type ValuesMap = HashMap<Rc<str>, Value, RandomState>;
 // type ValuesMap = HashMap<Rc<String>, Value>;
 
+/// Why a value couldn't be assigned to a name
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssignError {
+    Undefined,
+    Constant,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Environment {
     pub inner: Inner,
@@ -51,7 +58,7 @@ impl Environment {
         self.inner.get(key)
     }
 
-    pub fn assign(&mut self, key: Rc<str>, val: Value) -> bool {
+    pub fn assign(&mut self, key: Rc<str>, val: Value) -> Result<(), AssignError> {
         self.inner.assign(key, val)
     }
 
@@ -59,6 +66,18 @@ impl Environment {
         self.inner.define(key, value);
     }
 
+    /// Define a binding that can never be assigned to again
+    pub fn define_constant(&mut self, key: Rc<str>, value: Value) {
+        self.inner.define(key.clone(), value);
+        self.inner.constants.insert(key);
+    }
+
+    /// Whether `key` is a constant defined in this scope, ignoring any
+    /// enclosing ones
+    pub fn is_constant(&self, key: &str) -> bool {
+        self.inner.constants.contains(key)
+    }
+
     pub fn clone_values(&self) -> ValuesMap {
         self.inner.values.clone()
     }
@@ -68,6 +87,7 @@ impl Environment {
 pub struct Inner {
     enclosing: Option<Rc<RefCell<Environment>>>,
     pub values: ValuesMap,
+    constants: HashSet<Rc<str>, RandomState>,
 }
 
 impl Inner {
@@ -75,10 +95,12 @@ impl Inner {
         Self {
             enclosing: None,
             values: HashMap::default(),
+            constants: HashSet::default(),
         }
     }
 
     fn define(&mut self, name: Rc<str>, value: Value) {
+        self.constants.remove(&name);
         self.values.insert(name, value);
     }
 
@@ -92,15 +114,19 @@ impl Inner {
         }
     }
 
-    fn assign(&mut self, name: Rc<str>, value: Value) -> bool {
+    fn assign(&mut self, name: Rc<str>, value: Value) -> Result<(), AssignError> {
+        if self.constants.contains(&name) {
+            return Err(AssignError::Constant);
+        }
+
         match self.values.entry(name.clone()) {
             Entry::Vacant(_) => match &mut self.enclosing {
-                None => false,
+                None => Err(AssignError::Undefined),
                 Some(enclosing) => enclosing.borrow_mut().assign(name, value),
             },
             Entry::Occupied(mut entry) => {
                 entry.insert(value);
-                true
+                Ok(())
             }
         }
     }
@@ -109,6 +135,7 @@ impl Inner {
         Self {
             enclosing: Some(enclosing),
             values: HashMap::default(),
+            constants: HashSet::default(),
         }
     }
 }
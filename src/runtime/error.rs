@@ -2,24 +2,112 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum RuntimeError {
-    #[error("[{0}] {1}")]
+    #[error("{1}")]
     Syntax(usize, String),
-    #[error("[{0}] INVALID BREAK, FIX IT FUCKWIT.")]
+    #[error("INVALID BREAK, FIX IT FUCKWIT.")]
     InvalidBreak(usize),
-    #[error("[{0}] SORRY MATE! YA CAN ONLY CALL FUNCTIONS, YA DAFT BUGGER!")]
+    #[error("INVALID SHE'LL BE RIGHT, FIX IT FUCKWIT.")]
+    InvalidContinue(usize),
+    #[error("SORRY MATE! YA CAN ONLY CALL FUNCTIONS, YA DAFT BUGGER!")]
     InvalidCallee(usize),
-    #[error("[{0}] OI MATE, CAN YA FUCKIN' COUNT?? EXPECTED {1} ARGUMENTS BUT GOT {2}")]
+    #[error("OI MATE, CAN YA FUCKIN' COUNT?? EXPECTED {1} ARGUMENTS BUT GOT {2}")]
     InvalidArity(usize, u8, usize),
-    #[error("[{0}] CAN'T FIND THE IMPORT {1}")]
+    #[error("CAN'T FIND THE IMPORT {1}")]
     UnknownImport(usize, String),
     #[error("{0}")]
     General(String),
-    #[error("[{0}] SORRY C***! '{1}' ISN'T DEFINED, YA DAFT BUGGER!")]
+    #[error("SORRY C***! '{1}' ISN'T DEFINED, YA DAFT BUGGER!")]
     UndefinedVariable(usize, String),
+    #[error("FAIR SUCK OF THE SAV! YA CAN'T INDEX INTO A {1}")]
+    NotIndexable(usize, &'static str),
+    #[error("OI MATE! {1} ISN'T A VALID INDEX, IT'S GOTTA BE A WHOLE NUMBER >= 0")]
+    InvalidIndex(usize, String),
+    #[error("STREWTH! INDEX {1} IS OUT OF BOUNDS, IT'S ONLY GOT {2} ITEMS")]
+    IndexOutOfBounds(usize, usize, usize),
+    #[error("HOPPIN BY 0 WOULD TAKE YA FOREVER, YA DRONGO!")]
+    ZeroStep(usize),
+    #[error("YA CAN'T GO WALKABOUT THROUGH A {1}, YA GALAH!")]
+    NotIterable(usize, &'static str),
+    #[error("CRIKEY! A {1} CAN'T BE USED AS A MAP KEY")]
+    InvalidKey(usize, &'static str),
+    #[error("STREWTH! SOMEONE CHUCKED A WOBBLY AND NO ONE CAUGHT IT: {1}")]
+    Thrown(usize, String),
+    #[error("FAIR DINKUM, NOTHING MATCHED {1} AND THERE'S NO CATCH-ALL")]
+    NoMatch(usize, String),
+    #[error("STONE THE CROWS! {1} IS TOO BIG FOR AN INTEGER")]
+    Overflow(usize, String),
+    #[error("YA CAN'T DIVIDE BY ZERO, YA DRONGO!")]
+    DivideByZero(usize),
+    #[error("OI MATE! {1} ONLY WORKS ON WHOLE NUMBERS, NOT {2}")]
+    NotAnInteger(usize, String, String),
+    #[error("OI, YA CAN'T REDEFINE {1}, IT'S A CONSTANT!")]
+    AssignToConstant(usize, String),
+    #[error("OI MATE, EXPECTED A MAP BUT GOT A {1}")]
+    NotAMap(usize, &'static str),
+    #[error("OI MATE! YA CAN'T SHIFT BY {1}, IT'S GOTTA BE FROM 0 TO 63")]
+    InvalidShift(usize, i64),
+    #[error("OI MATE, A {1} DOESN'T HAVE A LENGTH")]
+    NoLength(usize, &'static str),
 }
 
 impl RuntimeError {
     pub fn new_syntax<T: Into<String>>(msg: T, line: usize) -> RuntimeError {
         RuntimeError::Syntax(line, msg.into())
     }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::General(_) => "E000",
+            Self::Syntax(..) => "E001",
+            Self::InvalidBreak(_) => "E002",
+            Self::InvalidContinue(_) => "E003",
+            Self::InvalidCallee(_) => "E004",
+            Self::InvalidArity(..) => "E005",
+            Self::UnknownImport(..) => "E006",
+            Self::UndefinedVariable(..) => "E007",
+            Self::NotIndexable(..) => "E008",
+            Self::InvalidIndex(..) => "E009",
+            Self::IndexOutOfBounds(..) => "E010",
+            Self::ZeroStep(_) => "E011",
+            Self::NotIterable(..) => "E012",
+            Self::InvalidKey(..) => "E013",
+            Self::Thrown(..) => "E014",
+            Self::NoMatch(..) => "E015",
+            Self::Overflow(..) => "E016",
+            Self::DivideByZero(_) => "E017",
+            Self::NotAnInteger(..) => "E018",
+            Self::AssignToConstant(..) => "E019",
+            Self::NotAMap(..) => "E020",
+            Self::InvalidShift(..) => "E021",
+            Self::NoLength(..) => "E022",
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::General(_) => None,
+            Self::Syntax(line, _)
+            | Self::InvalidBreak(line)
+            | Self::InvalidContinue(line)
+            | Self::InvalidCallee(line)
+            | Self::InvalidArity(line, _, _)
+            | Self::UnknownImport(line, _)
+            | Self::UndefinedVariable(line, _)
+            | Self::NotIndexable(line, _)
+            | Self::InvalidIndex(line, _)
+            | Self::IndexOutOfBounds(line, _, _)
+            | Self::ZeroStep(line)
+            | Self::NotIterable(line, _)
+            | Self::InvalidKey(line, _)
+            | Self::Thrown(line, _)
+            | Self::NoMatch(line, _)
+            | Self::Overflow(line, _)
+            | Self::DivideByZero(line)
+            | Self::NotAnInteger(line, _, _)
+            | Self::AssignToConstant(line, _)
+            | Self::NotAMap(line, _)
+            | Self::InvalidShift(line, _)
+            | Self::NoLength(line, _) => Some(*line),
+        }
+    }
 }
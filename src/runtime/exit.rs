@@ -1,7 +1,12 @@
+use std::rc::Rc;
+
 use super::Value;
 
 pub enum ExitKind {
-    Break(usize),
+    /// Line and the label of the loop to break out of, if any
+    Break(usize, Option<Rc<str>>),
+    /// Line and the label of the loop to continue, if any
+    Continue(usize, Option<Rc<str>>),
     Return(Value),
 }
 
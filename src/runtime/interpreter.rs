@@ -3,34 +3,50 @@ use arrayvec::ArrayVec;
 use itertools::Itertools;
 use std::{
     cell::RefCell,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
     fmt::Arguments,
     io::{stdout, Write},
     mem,
     ops::Add,
+    path::PathBuf,
     process,
     rc::Rc,
 };
 
 use crate::{
     ast::{
-        BinaryOp, Expr, ExprNode, ForLoop, If, LogicalOp, Match, Pattern, Range, Stmt, UnaryOp,
-        Var, VarDecl, WhileLoop,
+        BinaryOp, Expr, ExprNode, ForEachLoop, ForLoop, Ident, If, LogicalOp, Match, MatchBranch,
+        ModuleImport, Pattern, Range, RangeBound, Step, Stmt, TryCatch, UnaryOp, Var, VarDecl,
+        WhileLoop,
     },
+    diagnostic::Diagnostic,
     parser::error::ParseError,
     runtime::AussieCallable,
+    span::Span,
     token::Token,
 };
 
 use super::{
-    environment::Environment,
+    environment::{AssignError, Environment},
     error::RuntimeError,
     exit::{Exit, ExitKind},
-    BuiltIn, Callable, RuntimePartialEq, UserDefined, Value, MAX_ARITY,
+    BuiltIn, Callable, Key, RuntimePartialEq, UserDefined, Value, MAX_ARITY,
 };
 
 pub struct Interpreter<'a> {
     writer: Option<&'a mut dyn Write>,
     env: Rc<RefCell<Environment>>,
+    // Values aren't `Send` so they can't go in an error, instead a
+    // thrown value waits here until it's caught
+    thrown: Option<Value>,
+    // Where the error on its way out happened, so its diagnostic can point
+    // at it. It's the innermost expression that failed, since that's the
+    // one to blame
+    error_span: Option<Span>,
+    // Environments of the modules that have already been run
+    modules: HashMap<PathBuf, Rc<RefCell<Environment>>>,
 }
 
 impl<'a> Default for Interpreter<'a> {
@@ -44,6 +60,9 @@ impl<'a> Interpreter<'a> {
         Self {
             writer: None,
             env: Rc::new(RefCell::new(Environment::default())),
+            thrown: None,
+            error_span: None,
+            modules: HashMap::new(),
         }
     }
 
@@ -51,6 +70,9 @@ impl<'a> Interpreter<'a> {
         Interpreter {
             writer: Some(writer),
             env: Rc::new(RefCell::new(Environment::default())),
+            thrown: None,
+            error_span: None,
+            modules: HashMap::new(),
         }
     }
 
@@ -58,7 +80,17 @@ impl<'a> Interpreter<'a> {
         self.env.clone()
     }
 
-    fn print(&mut self, args: Arguments<'_>) {
+    /// Forget every variable and module, starting over with a fresh
+    /// global environment
+    pub fn reset(&mut self) {
+        self.env = Rc::new(RefCell::new(Environment::default()));
+        self.thrown = None;
+        self.error_span = None;
+        self.modules.clear();
+    }
+
+    /// Print a line to stdout, and to the writer if there is one
+    pub fn print(&mut self, args: Arguments<'_>) {
         use std::borrow::BorrowMut;
         let w = self.writer.borrow_mut();
         if let Some(w) = w {
@@ -68,18 +100,86 @@ impl<'a> Interpreter<'a> {
         stdout().flush().unwrap();
     }
 
-    pub fn interpret(&mut self, stmts: Vec<Stmt>) -> Result<()> {
+    /// Print a line to stderr, and to the writer if there is one
+    pub fn print_err(&mut self, args: Arguments<'_>) {
+        if let Some(w) = &mut self.writer {
+            let _ = writeln!(w, "{}", args);
+        }
+        eprintln!("{}", args);
+    }
+
+    /// Like `interpret`, but also prints the value of a trailing expression
+    /// statement unless it's an assignment or evaluates to nil
+    pub fn interpret_echo(
+        &mut self,
+        mut stmts: Vec<Stmt>,
+    ) -> std::result::Result<(), Vec<Diagnostic>> {
+        let echo = match stmts.last() {
+            Some(Stmt::Expr(node)) => !matches!(
+                node.expr(),
+                Expr::Assign(..) | Expr::IndexAssign(..) | Expr::IndexUpdate(..)
+            ),
+            _ => false,
+        };
+        let last = if echo { stmts.pop() } else { None };
+
+        let mut diagnostics = self.interpret(stmts).err().unwrap_or_default();
+
+        if let Some(Stmt::Expr(node)) = last {
+            match self.evaluate(&node) {
+                Ok(Value::Nil) => {}
+                Ok(val) => self.print(format_args!("{}", val)),
+                Err(e) => diagnostics.push(self.diagnostic(&e)),
+            }
+        }
+
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Run `stmts`, carrying on past any statement that fails. Whatever went
+    /// wrong is handed back once they've all had a go
+    pub fn interpret(&mut self, stmts: Vec<Stmt>) -> std::result::Result<(), Vec<Diagnostic>> {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
         for stmt in stmts {
             match self.execute_stmt(&stmt) {
-                Err(e) => eprintln!("{}", e),
+                Err(e) => diagnostics.push(self.diagnostic(&e)),
                 Ok(None) => {}
-                Ok(Some(ExitKind::Break(line))) => {
-                    return Err(RuntimeError::InvalidBreak(line).into())
+                Ok(Some(ExitKind::Break(line, _))) => {
+                    diagnostics.push((&RuntimeError::InvalidBreak(line)).into());
+                    break;
+                }
+                Ok(Some(ExitKind::Continue(line, _))) => {
+                    diagnostics.push((&RuntimeError::InvalidContinue(line)).into());
+                    break;
                 }
-                Ok(Some(_)) => return Ok(()),
+                Ok(Some(_)) => break,
             };
         }
-        Ok(())
+
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// The diagnostic for an error that made it all the way out, pointing
+    /// at where it happened if that's known
+    fn diagnostic(&mut self, e: &anyhow::Error) -> Diagnostic {
+        // No one caught it, so there's no one left to hand the value to
+        if let Some(RuntimeError::Thrown(..)) = e.downcast_ref::<RuntimeError>() {
+            self.thrown = None;
+        }
+        let diagnostic: Diagnostic = e.into();
+        match self.error_span.take() {
+            Some(span) => diagnostic.with_span(span),
+            None => diagnostic,
+        }
     }
 
     fn execute_stmt(&mut self, stmt: &Stmt) -> Result<Exit> {
@@ -100,6 +200,7 @@ impl<'a> Interpreter<'a> {
                 };
                 Ok(None)
             }
+            Stmt::ImportModule(import) => self.execute_import_module(import),
             Stmt::Exit(fuckinpiker) => {
                 if *fuckinpiker {
                     process::exit(1)
@@ -120,9 +221,25 @@ impl<'a> Interpreter<'a> {
 
                 Ok(None)
             }
-            Stmt::Break(tok) => Ok(Some(ExitKind::Break(tok.line()))),
+            Stmt::Break(tok, label) => Ok(Some(ExitKind::Break(
+                tok.line(),
+                label.as_ref().map(|label| label.name.clone()),
+            ))),
+            Stmt::Continue(tok, label) => Ok(Some(ExitKind::Continue(
+                tok.line(),
+                label.as_ref().map(|label| label.name.clone()),
+            ))),
             Stmt::While(while_loop) => self.execute_while_loop(while_loop),
             Stmt::For(for_loop) => self.execute_for_loop(for_loop),
+            Stmt::ForEach(for_each) => self.execute_for_each_loop(for_each),
+            Stmt::Try(try_catch) => self.execute_try_catch(try_catch),
+            Stmt::Throw(tok, expr) => {
+                let val = self.evaluate(expr)?;
+                let err = RuntimeError::Thrown(tok.line(), val.to_string());
+                self.thrown = Some(val);
+                self.error_span = Some(tok.span().to(expr.span()));
+                Err(err.into())
+            }
             Stmt::Print(expr) => {
                 let val = self.evaluate(expr)?;
                 self.print(format_args!("{}", val));
@@ -134,14 +251,30 @@ impl<'a> Interpreter<'a> {
                 Ok(None)
             }
             Stmt::VarDecl(VarDecl {
-                ident, initializer, ..
+                ident,
+                initializer,
+                immutable,
             }) => {
                 let value = match initializer {
                     None => Value::Nil,
                     Some(expr_node) => self.evaluate(expr_node)?,
                 };
 
-                self.env.borrow_mut().define(ident.name.clone(), value);
+                let mut env = self.env.borrow_mut();
+                // The resolver catches this within a program, but a REPL
+                // resolves each line on its own
+                if env.is_constant(&ident.name) {
+                    return Err(RuntimeError::AssignToConstant(
+                        ident.line(),
+                        ident.name.to_string(),
+                    )
+                    .into());
+                }
+                if *immutable {
+                    env.define_constant(ident.name.clone(), value);
+                } else {
+                    env.define(ident.name.clone(), value);
+                }
 
                 Ok(None)
             }
@@ -175,30 +308,36 @@ impl<'a> Interpreter<'a> {
         } = m;
         let val = self.evaluate(val)?;
 
-        for branch in branches {
-            if branch.pat.runtime_eq(&val) {
-                return self.execute_block(
-                    &branch.body,
-                    Rc::new(RefCell::new(Environment::new_with_enclosing(self.env()))),
-                );
+        for branch in branches.iter().chain(default) {
+            if let Some(env) = self.match_branch(branch, &val)? {
+                return self.execute_block(&branch.body, env);
             }
         }
 
-        match default {
-            Some(branch) if !branch.pat.runtime_eq(&val) => {
-                let mut env = Environment::new_with_enclosing(self.env());
-                let var = if let Pattern::Var(v) = &branch.pat {
-                    Some(v)
-                } else {
-                    None
-                };
+        Ok(None)
+    }
 
-                env.define(var.unwrap().name().clone(), val);
+    /// If `branch` matches `val`, returns the environment to run its body in
+    fn match_branch<T>(
+        &mut self,
+        branch: &MatchBranch<T>,
+        val: &Value,
+    ) -> Result<Option<Rc<RefCell<Environment>>>> {
+        let mut env = Environment::new_with_enclosing(self.env());
+        match &branch.pat {
+            Pattern::Var(var) => env.define(var.name().clone(), val.clone()),
+            pat if pat.runtime_eq(val) => {}
+            _ => return Ok(None),
+        }
+        let env = Rc::new(RefCell::new(env));
 
-                self.execute_block(&branch.body, Rc::new(RefCell::new(env)))
+        if let Some(guard) = &branch.guard {
+            if !Self::is_truthy(&self.evaluate_in(guard, env.clone())?) {
+                return Ok(None);
             }
-            _ => Ok(None),
         }
+
+        Ok(Some(env))
     }
 
     fn execute_while_loop(&mut self, while_loop: &WhileLoop) -> Result<Exit> {
@@ -209,7 +348,16 @@ impl<'a> Interpreter<'a> {
                         for stmt in &while_loop.body {
                             match self.execute_stmt(stmt)? {
                                 None => {}
-                                Some(ExitKind::Break(_)) => return Ok(None),
+                                Some(ExitKind::Break(_, target))
+                                    if Self::is_loop_target(&target, &while_loop.label) =>
+                                {
+                                    return Ok(None)
+                                }
+                                Some(ExitKind::Continue(_, target))
+                                    if Self::is_loop_target(&target, &while_loop.label) =>
+                                {
+                                    break
+                                }
                                 ret => return Ok(ret),
                             }
                         }
@@ -221,7 +369,16 @@ impl<'a> Interpreter<'a> {
                     for stmt in &while_loop.body {
                         match self.execute_stmt(stmt)? {
                             None => {}
-                            Some(ExitKind::Break(_)) => return Ok(None),
+                            Some(ExitKind::Break(_, target))
+                                if Self::is_loop_target(&target, &while_loop.label) =>
+                            {
+                                return Ok(None)
+                            }
+                            Some(ExitKind::Continue(_, target))
+                                if Self::is_loop_target(&target, &while_loop.label) =>
+                            {
+                                break
+                            }
                             ret => return Ok(ret),
                         }
                     }
@@ -233,81 +390,241 @@ impl<'a> Interpreter<'a> {
     }
 
     fn execute_for_loop(&mut self, for_loop: &ForLoop) -> Result<Exit> {
-        let mut env = Environment::new_with_enclosing(self.env());
-        let start = match self.evaluate(for_loop.range.0.expr())? {
-            Value::Number(n) => n,
-            other => {
-                return Err(ParseError::InvalidRange(
-                    for_loop.var.line(),
-                    "start".into(),
-                    other.into(),
-                )
-                .into())
-            }
-        };
-        let end = match self.evaluate(for_loop.range.1.expr())? {
-            Value::Number(n) => n,
-            other => {
-                let line = for_loop.var.line();
-                return Err(ParseError::InvalidRange(line, "start".into(), other.into()).into());
-            }
+        let line = for_loop.var.line();
+        let start = self.evaluate(for_loop.range.0.expr())?;
+        let end = self.evaluate(for_loop.range.1.expr())?;
+        let step = match &for_loop.step {
+            None => Value::Integer(1),
+            Some(step) => self.evaluate(step)?,
         };
+        if step.as_f64() == Some(0f64) {
+            return Err(RuntimeError::ZeroStep(line).into());
+        }
+
+        // Walk with integers when we can so big numbers don't lose precision
+        if let (Value::Integer(start), Value::Integer(end), Value::Integer(step)) =
+            (&start, &end, &step)
+        {
+            let range = (
+                for_loop.range.0.to_evaluated(*start),
+                for_loop.range.1.to_evaluated(*end),
+                *step,
+            );
+            return self.walk_range(for_loop, range, Value::Integer);
+        }
 
         let range = (
-            for_loop.range.0.to_evaluated(start),
-            for_loop.range.1.to_evaluated(end),
+            for_loop
+                .range
+                .0
+                .to_evaluated(Self::range_number(start, "start", line)?),
+            for_loop
+                .range
+                .1
+                .to_evaluated(Self::range_number(end, "end", line)?),
+            Self::range_number(step, "step", line)?,
         );
+        self.walk_range(for_loop, range, Value::Number)
+    }
 
+    fn walk_range<T: Step>(
+        &mut self,
+        for_loop: &ForLoop,
+        range: (RangeBound<T>, RangeBound<T>, T),
+        to_value: fn(T) -> Value,
+    ) -> Result<Exit> {
+        let mut env = Environment::new_with_enclosing(self.env());
         let (mut i, _) = range.values();
 
         let var_name = for_loop.var.name();
-        env.define(var_name.clone(), Value::Number(i));
+        env.define(var_name.clone(), to_value(i));
 
         let env = Rc::new(RefCell::new(env));
 
         while range.satisfied(i) {
             match self.execute_block(&for_loop.body, env.clone())? {
                 None => {}
-                Some(ExitKind::Break(_)) => break,
+                Some(ExitKind::Break(_, target))
+                    if Self::is_loop_target(&target, &for_loop.label) =>
+                {
+                    break
+                }
+                Some(ExitKind::Continue(_, target))
+                    if Self::is_loop_target(&target, &for_loop.label) => {}
                 Some(other) => return Ok(Some(other)),
             };
-            range.iterate(&mut i);
-            env.borrow_mut().assign(var_name.clone(), Value::Number(i));
+            if !range.iterate(&mut i) {
+                break;
+            }
+            env.borrow_mut().define(var_name.clone(), to_value(i));
         }
 
         Ok(None)
     }
 
-    pub fn execute_block(&mut self, stmts: &[Stmt], env: Rc<RefCell<Environment>>) -> Result<Exit> {
-        let previous = mem::replace(&mut self.env, env);
+    fn range_number(val: Value, which: &str, line: usize) -> Result<f64> {
+        match val.as_f64() {
+            Some(n) => Ok(n),
+            None => Err(ParseError::InvalidRange(line, which.into(), val.into()).into()),
+        }
+    }
 
-        for stmt in stmts {
-            match self.execute_stmt(stmt)? {
+    fn execute_for_each_loop(&mut self, for_each: &ForEachLoop) -> Result<Exit> {
+        let mut env = Environment::new_with_enclosing(self.env());
+        let line = for_each.var.line();
+
+        // Take a snapshot of the items up front so the body is free to
+        // change the collection it's walking through
+        let items: Vec<Value> = match self.evaluate(&for_each.iterable)? {
+            Value::String(s) => s.chars().map(|c| Value::String(c.to_string())).collect(),
+            Value::List(list) => list.borrow().clone(),
+            Value::Map(map) => map.borrow().keys().cloned().map(Value::from).collect(),
+            other => return Err(RuntimeError::NotIterable(line, other.type_name()).into()),
+        };
+
+        let var_name = for_each.var.name();
+        env.define(var_name.clone(), Value::Nil);
+
+        let env = Rc::new(RefCell::new(env));
+
+        for item in items {
+            env.borrow_mut().define(var_name.clone(), item);
+            match self.execute_block(&for_each.body, env.clone())? {
                 None => {}
-                Some(s) => {
-                    self.env = previous;
-                    return Ok(Some(s));
+                Some(ExitKind::Break(_, target))
+                    if Self::is_loop_target(&target, &for_each.label) =>
+                {
+                    break
                 }
+                Some(ExitKind::Continue(_, target))
+                    if Self::is_loop_target(&target, &for_each.label) => {}
+                Some(other) => return Ok(Some(other)),
             };
         }
 
-        self.env = previous;
+        Ok(None)
+    }
+
+    /// Whether a loop with `label` should handle a break or continue aimed at `target`.
+    /// Unlabelled ones are always handled by the innermost loop
+    fn is_loop_target(target: &Option<Rc<str>>, label: &Option<Ident>) -> bool {
+        match target {
+            None => true,
+            Some(target) => matches!(label, Some(label) if label.name == *target),
+        }
+    }
+
+    fn execute_import_module(&mut self, import: &ModuleImport) -> Result<Exit> {
+        let module = match &import.module {
+            Some(module) => module,
+            None => {
+                return Err(RuntimeError::UnknownImport(import.line, import.path.clone()).into())
+            }
+        };
+
+        let module_env = match self.modules.get(&module.path) {
+            Some(env) => env.clone(),
+            None => {
+                let env = Rc::new(RefCell::new(Environment::default()));
+                if let Err(e) = self.execute_block(&module.stmts, env.clone()) {
+                    // Spans in the module are into its own source, so point
+                    // at the import instead
+                    self.error_span = Some(import.span);
+                    return Err(e);
+                }
+                self.modules.insert(module.path.clone(), env.clone());
+                env
+            }
+        };
+
+        // Imports can't be assigned to, so a module's constants stay constant
+        for export in &module.exports {
+            let val = module_env.borrow().get(&export.name).unwrap_or(Value::Nil);
+            self.env
+                .borrow_mut()
+                .define_constant(export.name.clone(), val);
+        }
+
+        Ok(None)
+    }
+
+    fn execute_try_catch(&mut self, try_catch: &TryCatch) -> Result<Exit> {
+        for stmt in &try_catch.body {
+            match self.execute_stmt(stmt) {
+                Ok(None) => {}
+                Ok(exit) => return Ok(exit),
+                Err(e) => {
+                    self.error_span = None;
+                    let mut env = Environment::new_with_enclosing(self.env());
+                    env.define(try_catch.var.name.clone(), self.caught_value(e));
+
+                    return self.execute_block(&try_catch.handler, Rc::new(RefCell::new(env)));
+                }
+            }
+        }
 
         Ok(None)
     }
+
+    /// The value a handler receives: whatever was thrown, or a map
+    /// with the `message` and `line` of any other error
+    fn caught_value(&mut self, err: anyhow::Error) -> Value {
+        let runtime_err = err.downcast_ref::<RuntimeError>();
+        if let Some(RuntimeError::Thrown(..)) = runtime_err {
+            if let Some(val) = self.thrown.take() {
+                return val;
+            }
+        }
+
+        let line = runtime_err
+            .and_then(RuntimeError::line)
+            .or_else(|| err.downcast_ref::<ParseError>().and_then(ParseError::line));
+
+        let mut map = BTreeMap::new();
+        map.insert(Key::String("message".into()), err.to_string().into());
+        map.insert(
+            Key::String("line".into()),
+            line.map_or(Value::Nil, |line| Value::Integer(line as i64)),
+        );
+        map.into()
+    }
+
+    pub fn execute_block(&mut self, stmts: &[Stmt], env: Rc<RefCell<Environment>>) -> Result<Exit> {
+        let previous = mem::replace(&mut self.env, env);
+
+        let mut exit = Ok(None);
+        for stmt in stmts {
+            exit = self.execute_stmt(stmt);
+            if !matches!(exit, Ok(None)) {
+                break;
+            }
+        }
+
+        // Restore the environment even if a statement failed, otherwise
+        // a caught error would leave us stuck in the inner scope
+        self.env = previous;
+
+        exit
+    }
 }
 
 impl<'a> Interpreter<'a> {
     pub fn evaluate(&mut self, node: &ExprNode) -> Result<Value> {
+        let val = self.evaluate_expr(node);
+        if val.is_err() && self.error_span.is_none() {
+            self.error_span = Some(node.span());
+        }
+        val
+    }
+
+    fn evaluate_expr(&mut self, node: &ExprNode) -> Result<Value> {
         match node.expr() {
             Expr::Call(expr_callee, token, params) => {
                 self.evaluate_call(expr_callee, token, params)
             }
             Expr::Assign(ref var, ref expr) => {
                 let value = self.evaluate(expr)?;
-                self.env
-                    .borrow_mut()
-                    .assign(var.name().clone(), value.clone());
+                self.assign(var, value.clone())?;
                 Ok(value)
             }
             Expr::Var(ref var) => self.lookup(var).map_or_else(
@@ -329,6 +646,21 @@ impl<'a> Interpreter<'a> {
                     (UnaryOp::Minus, Value::Number(right)) => Value::Number(right * -1f64),
                     (UnaryOp::Incr, Value::Number(right)) => Value::Number(right + 1f64),
                     (UnaryOp::Decr, Value::Number(right)) => Value::Number(right - 1f64),
+                    (UnaryOp::Minus, Value::Integer(right)) => {
+                        Self::checked_integer(right.checked_neg(), expr.line(), || {
+                            format!("-({})", right)
+                        })?
+                    }
+                    (UnaryOp::Incr, Value::Integer(right)) => {
+                        Self::checked_integer(right.checked_add(1), expr.line(), || {
+                            format!("{} + 1", right)
+                        })?
+                    }
+                    (UnaryOp::Decr, Value::Integer(right)) => {
+                        Self::checked_integer(right.checked_sub(1), expr.line(), || {
+                            format!("{} - 1", right)
+                        })?
+                    }
                     _ => {
                         return Err(RuntimeError::new_syntax(
                             "invalid unary operation",
@@ -338,8 +670,9 @@ impl<'a> Interpreter<'a> {
                     }
                 };
 
-                if let Expr::Var(v) = expr.expr() {
-                    self.env.borrow_mut().assign(v.name().clone(), val.clone());
+                // Only incrementing and decrementing change the variable
+                if let (UnaryOp::Incr | UnaryOp::Decr, Expr::Var(v)) = (op, expr.expr()) {
+                    self.assign(v, val.clone())?;
                 }
 
                 Ok(val)
@@ -372,6 +705,137 @@ impl<'a> Interpreter<'a> {
             Expr::Binary(ref left_expr, op, ref right_expr) => {
                 self.evaluate_binary(left_expr, op, right_expr)
             }
+            Expr::List(items) => {
+                let mut list = Vec::with_capacity(items.len());
+                for item in items {
+                    list.push(self.evaluate(item)?);
+                }
+                Ok(list.into())
+            }
+            Expr::Map(entries) => {
+                let mut map = BTreeMap::new();
+                for (key, value) in entries {
+                    let line = key.line();
+                    let key = Self::unwrap_key(&self.evaluate(key)?, line)?;
+                    map.insert(key, self.evaluate(value)?);
+                }
+                Ok(map.into())
+            }
+            Expr::Index(target, index) => self.evaluate_index(target, index),
+            Expr::IndexAssign(target, index, value) => {
+                self.evaluate_index_assign(target, index, value)
+            }
+            Expr::IndexUpdate(target, index, op, value) => {
+                self.evaluate_index_update(target, index, op, value)
+            }
+            Expr::Match(m) => self.evaluate_match(m),
+            Expr::Interpolation(parts) => {
+                let mut s = String::new();
+                for part in parts {
+                    s.push_str(&self.evaluate(part)?.to_string());
+                }
+                Ok(Value::String(s))
+            }
+            Expr::Lambda(decl) => {
+                let function: Callable = UserDefined::new(decl.clone(), self.env.clone()).into();
+                Ok(Value::Callable(Rc::new(function)))
+            }
+        }
+    }
+
+    fn evaluate_match(&mut self, m: &Match<ExprNode>) -> Result<Value> {
+        let val = self.evaluate(&m.val)?;
+
+        for branch in m.branches.iter().chain(&m.default) {
+            if let Some(env) = self.match_branch(branch, &val)? {
+                return self.evaluate_in(&branch.body, env);
+            }
+        }
+
+        Err(RuntimeError::NoMatch(m.val.line(), val.to_string()).into())
+    }
+
+    /// Evaluate `node` inside `env` rather than the current environment
+    fn evaluate_in(&mut self, node: &ExprNode, env: Rc<RefCell<Environment>>) -> Result<Value> {
+        let previous = mem::replace(&mut self.env, env);
+        let val = self.evaluate(node);
+        self.env = previous;
+        val
+    }
+
+    fn evaluate_index(&mut self, target: &ExprNode, index: &ExprNode) -> Result<Value> {
+        let line = target.line();
+        let target = self.evaluate(target)?;
+        let index = self.evaluate(index)?;
+
+        Self::index(target, index, line)
+    }
+
+    fn index(target: Value, index: Value, line: usize) -> Result<Value> {
+        match target {
+            Value::List(list) => {
+                let list = list.borrow();
+                let i = Self::unwrap_index(index, list.len(), line)?;
+                Ok(list[i].clone())
+            }
+            Value::String(s) => {
+                let i = Self::unwrap_index(index, s.chars().count(), line)?;
+                Ok(Value::String(s.chars().nth(i).unwrap().to_string()))
+            }
+            Value::Map(map) => {
+                let key = Self::unwrap_key(&index, line)?;
+                Ok(map.borrow().get(&key).cloned().unwrap_or(Value::Nil))
+            }
+            other => Err(RuntimeError::NotIndexable(line, other.type_name()).into()),
+        }
+    }
+
+    fn evaluate_index_assign(
+        &mut self,
+        target: &ExprNode,
+        index: &ExprNode,
+        value: &ExprNode,
+    ) -> Result<Value> {
+        let line = target.line();
+        let target = self.evaluate(target)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+
+        Self::index_assign(target, index, value, line)
+    }
+
+    fn evaluate_index_update(
+        &mut self,
+        target: &ExprNode,
+        index: &ExprNode,
+        op: &BinaryOp,
+        value: &ExprNode,
+    ) -> Result<Value> {
+        let line = target.line();
+        let target = self.evaluate(target)?;
+        let index = self.evaluate(index)?;
+
+        let current = Self::index(target.clone(), index.clone(), line)?;
+        let value = self.evaluate(value)?;
+        let value = self.binary(current, op, value, line)?;
+
+        Self::index_assign(target, index, value, line)
+    }
+
+    fn index_assign(target: Value, index: Value, value: Value, line: usize) -> Result<Value> {
+        match target {
+            Value::List(list) => {
+                let mut list = list.borrow_mut();
+                let i = Self::unwrap_index(index, list.len(), line)?;
+                list[i] = value.clone();
+                Ok(value)
+            }
+            Value::Map(map) => {
+                let key = Self::unwrap_key(&index, line)?;
+                map.borrow_mut().insert(key, value.clone());
+                Ok(value)
+            }
+            other => Err(RuntimeError::NotIndexable(line, other.type_name()).into()),
         }
     }
 
@@ -401,7 +865,7 @@ impl<'a> Interpreter<'a> {
             args.push(self.evaluate(arg)?);
         }
 
-        callable.call(self, &args)
+        callable.call(self, &args, token.line())
     }
 
     fn evaluate_binary(
@@ -414,9 +878,21 @@ impl<'a> Interpreter<'a> {
         let a = self.evaluate(left_expr)?;
         let b = self.evaluate(right_expr)?;
 
+        self.binary(a, op, b, line)
+    }
+
+    fn binary(&self, a: Value, op: &BinaryOp, b: Value, line: usize) -> Result<Value> {
         match op {
             BinaryOp::Plus => match (a, b) {
-                (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+                (
+                    a @ (Value::Number(_) | Value::Integer(_)),
+                    b @ (Value::Number(_) | Value::Integer(_)),
+                ) => Self::arithmetic(a, b, op, line, i64::checked_add, |a, b| a + b),
+                (Value::List(a), Value::List(b)) => {
+                    let mut list = a.borrow().clone();
+                    list.extend(b.borrow().iter().cloned());
+                    Ok(list.into())
+                }
                 (Value::String(a), Value::String(b)) => Ok(Value::String(a + &b)),
                 (Value::String(a), b) => Ok(Value::String(a.add(b.to_string().as_str()))),
                 (a, Value::String(b)) => Ok(Value::String(b.add(a.to_string().as_str()))),
@@ -426,38 +902,42 @@ impl<'a> Interpreter<'a> {
                 )
                 .into()),
             },
+            // The only remainder that overflows is `i64::MIN % -1`, which is 0
             BinaryOp::Modulo => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Number(a % b))
-            }
-            BinaryOp::Minus => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Number(a - b))
-            }
-            BinaryOp::Divide => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Number(a / b))
-            }
-            BinaryOp::Multiply => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Number(a * b))
-            }
-            BinaryOp::Greater => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Bool(a > b))
-            }
-            BinaryOp::GreaterEqual => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Bool(a >= b))
-            }
-            BinaryOp::Less => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Bool(a < b))
-            }
-            BinaryOp::LessEqual => {
-                let (a, b) = self.unwrap_nums(a, b, line)?;
-                Ok(Value::Bool(a <= b))
+                Self::arithmetic(a, b, op, line, |a, b| Some(a.wrapping_rem(b)), |a, b| a % b)
             }
+            BinaryOp::Minus => Self::arithmetic(a, b, op, line, i64::checked_sub, |a, b| a - b),
+            BinaryOp::Multiply => Self::arithmetic(a, b, op, line, i64::checked_mul, |a, b| a * b),
+            // Dividing always gives a float, `~/` is there for whole numbers
+            BinaryOp::Divide => match Self::unwrap_nums(a, b, line)? {
+                Nums::Integers(_, 0) => Err(RuntimeError::DivideByZero(line).into()),
+                Nums::Integers(a, b) => Ok(Value::Number(a as f64 / b as f64)),
+                Nums::Floats(a, b) => Ok(Value::Number(a / b)),
+            },
+            BinaryOp::FloorDivide => match Self::unwrap_nums(a, b, line)? {
+                Nums::Integers(_, 0) => Err(RuntimeError::DivideByZero(line).into()),
+                Nums::Integers(a, b) => {
+                    Self::checked_integer(Self::floor_div(a, b), line, || format!("{} ~/ {}", a, b))
+                }
+                Nums::Floats(a, b) => Ok(Value::Number((a / b).floor())),
+            },
+            BinaryOp::Power => match Self::unwrap_nums(a, b, line)? {
+                Nums::Integers(a, b) if b >= 0 => {
+                    let pow = u32::try_from(b).ok().and_then(|b| a.checked_pow(b));
+                    Self::checked_integer(pow, line, || format!("{} ** {}", a, b))
+                }
+                // A negative power of an integer is a fraction
+                Nums::Integers(a, b) => Ok(Value::Number((a as f64).powf(b as f64))),
+                Nums::Floats(a, b) => Ok(Value::Number(a.powf(b))),
+            },
+            BinaryOp::BitAnd => Self::bitwise(a, b, op, line, |a, b| Some(a & b)),
+            BinaryOp::BitOr => Self::bitwise(a, b, op, line, |a, b| Some(a | b)),
+            BinaryOp::BitXor => Self::bitwise(a, b, op, line, |a, b| Some(a ^ b)),
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => Self::shift(a, b, op, line),
+            BinaryOp::Greater => Self::compare(a, b, line, Ordering::is_gt),
+            BinaryOp::GreaterEqual => Self::compare(a, b, line, Ordering::is_ge),
+            BinaryOp::Less => Self::compare(a, b, line, Ordering::is_lt),
+            BinaryOp::LessEqual => Self::compare(a, b, line, Ordering::is_le),
             BinaryOp::NotEqual => Ok(Value::Bool(!self.is_equal(a, b))),
             BinaryOp::Equal => Ok(Value::Bool(self.is_equal(a, b))),
         }
@@ -475,6 +955,21 @@ impl<'a> Interpreter<'a> {
         }
     }
 
+    fn assign(&self, var: &Var, val: Value) -> Result<()> {
+        let name = var.name();
+        self.env
+            .borrow_mut()
+            .assign(name.clone(), val)
+            .map_err(|e| match e {
+                AssignError::Undefined => {
+                    RuntimeError::UndefinedVariable(var.line(), name.to_string()).into()
+                }
+                AssignError::Constant => {
+                    RuntimeError::AssignToConstant(var.line(), name.to_string()).into()
+                }
+            })
+    }
+
     fn is_truthy(val: &Value) -> bool {
         match val {
             Value::Bool(b) => *b,
@@ -484,6 +979,13 @@ impl<'a> Interpreter<'a> {
     }
 
     fn is_equal(&self, a: Value, b: Value) -> bool {
+        Self::equal_within(&a, &b, &mut Vec::new())
+    }
+
+    /// Compare `a` and `b`, where `comparing` holds the pairs of collections
+    /// being compared further out. Meeting one of those pairs again means the
+    /// collections contain themselves, and nothing so far says they differ
+    fn equal_within(a: &Value, b: &Value, comparing: &mut Vec<(*const (), *const ())>) -> bool {
         if let Value::Nil = a {
             if let Value::Nil = b {
                 return true;
@@ -493,16 +995,168 @@ impl<'a> Interpreter<'a> {
 
         match (a, b) {
             (Value::Number(a), Value::Number(b)) => (a - b).abs() < f64::EPSILON,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            // Exactly, since floats can't hold every integer past 2^53
+            (Value::Number(_), Value::Integer(n)) => a.as_i64() == Some(*n),
+            (Value::Integer(n), Value::Number(_)) => b.as_i64() == Some(*n),
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::List(a), Value::List(b)) => {
+                let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return true;
+                }
+                comparing.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                let equal = a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| Self::equal_within(a, b, comparing));
+                comparing.pop();
+                equal
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                let pair = (Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const ());
+                if Rc::ptr_eq(a, b) || comparing.contains(&pair) {
+                    return true;
+                }
+                comparing.push(pair);
+                let (a, b) = (a.borrow(), b.borrow());
+                let equal = a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|((ak, av), (bk, bv))| {
+                        ak == bk && Self::equal_within(av, bv, comparing)
+                    });
+                comparing.pop();
+                equal
+            }
             _ => false,
         }
     }
 
-    fn unwrap_nums(&self, a: Value, b: Value, line: usize) -> Result<(f64, f64)> {
+    fn unwrap_key(key: &Value, line: usize) -> Result<Key> {
+        Key::from_value(key).ok_or_else(|| RuntimeError::InvalidKey(line, key.type_name()).into())
+    }
+
+    fn unwrap_index(index: Value, len: usize, line: usize) -> Result<usize> {
+        let i = match index {
+            Value::Number(n) if n >= 0.0 && n.fract() == 0.0 => n as usize,
+            Value::Integer(n) if n >= 0 => n as usize,
+            other => return Err(RuntimeError::InvalidIndex(line, other.to_string()).into()),
+        };
+
+        if i >= len {
+            return Err(RuntimeError::IndexOutOfBounds(line, i, len).into());
+        }
+
+        Ok(i)
+    }
+
+    fn unwrap_nums(a: Value, b: Value, line: usize) -> Result<Nums> {
         match (a, b) {
-            (Value::Number(a), Value::Number(b)) => Ok((a, b)),
-            _ => Err((RuntimeError::new_syntax("THOSE AREN'T FUCKIN NUMBERS MATE!", line)).into()),
+            (Value::Integer(a), Value::Integer(b)) => Ok(Nums::Integers(a, b)),
+            (a, b) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => Ok(Nums::Floats(a, b)),
+                _ => Err(
+                    (RuntimeError::new_syntax("THOSE AREN'T FUCKIN NUMBERS MATE!", line)).into(),
+                ),
+            },
+        }
+    }
+
+    fn arithmetic(
+        a: Value,
+        b: Value,
+        op: &BinaryOp,
+        line: usize,
+        int_op: fn(i64, i64) -> Option<i64>,
+        float_op: fn(f64, f64) -> f64,
+    ) -> Result<Value> {
+        match Self::unwrap_nums(a, b, line)? {
+            Nums::Integers(_, 0) if matches!(op, BinaryOp::Modulo) => {
+                Err(RuntimeError::DivideByZero(line).into())
+            }
+            Nums::Integers(a, b) => {
+                Self::checked_integer(int_op(a, b), line, || format!("{} {} {}", a, op, b))
+            }
+            Nums::Floats(a, b) => Ok(Value::Number(float_op(a, b))),
         }
     }
+
+    /// Applies a bitwise operator, which only works on whole numbers
+    fn bitwise(
+        a: Value,
+        b: Value,
+        op: &BinaryOp,
+        line: usize,
+        int_op: fn(i64, i64) -> Option<i64>,
+    ) -> Result<Value> {
+        let (a, b) = (
+            Self::unwrap_integer(a, op, line)?,
+            Self::unwrap_integer(b, op, line)?,
+        );
+        Self::checked_integer(int_op(a, b), line, || format!("{} {} {}", a, op, b))
+    }
+
+    /// Shifts by a count from 0 to 63, anything else is an error of its own
+    /// rather than an overflow
+    fn shift(a: Value, b: Value, op: &BinaryOp, line: usize) -> Result<Value> {
+        let (a, b) = (
+            Self::unwrap_integer(a, op, line)?,
+            Self::unwrap_integer(b, op, line)?,
+        );
+        let count = u32::try_from(b)
+            .ok()
+            .filter(|count| *count < i64::BITS)
+            .ok_or(RuntimeError::InvalidShift(line, b))?;
+
+        if let BinaryOp::ShiftRight = op {
+            return Ok(Value::Integer(a >> count));
+        }
+        let shifted = a << count;
+        // Bits falling off the end is an overflow like any other
+        Self::checked_integer((shifted >> count == a).then_some(shifted), line, || {
+            format!("{} {} {}", a, op, b)
+        })
+    }
+
+    fn unwrap_integer(val: Value, op: &BinaryOp, line: usize) -> Result<i64> {
+        val.as_i64()
+            .ok_or_else(|| RuntimeError::NotAnInteger(line, op.to_string(), val.to_string()).into())
+    }
+
+    /// Integer division that rounds towards negative infinity, or `None` if
+    /// it overflows
+    fn floor_div(a: i64, b: i64) -> Option<i64> {
+        let quotient = a.checked_div(b)?;
+        if a % b != 0 && (a < 0) != (b < 0) {
+            Some(quotient - 1)
+        } else {
+            Some(quotient)
+        }
+    }
+
+    fn checked_integer(
+        result: Option<i64>,
+        line: usize,
+        expr: impl FnOnce() -> String,
+    ) -> Result<Value> {
+        result
+            .map(Value::Integer)
+            .ok_or_else(|| RuntimeError::Overflow(line, expr()).into())
+    }
+
+    fn compare(a: Value, b: Value, line: usize, pred: fn(Ordering) -> bool) -> Result<Value> {
+        let ordering = match Self::unwrap_nums(a, b, line)? {
+            Nums::Integers(a, b) => Some(a.cmp(&b)),
+            Nums::Floats(a, b) => a.partial_cmp(&b),
+        };
+        Ok(Value::Bool(ordering.is_some_and(pred)))
+    }
+}
+
+/// The operands of a numeric operator. Integers are only kept as integers if
+/// both sides are, otherwise they're promoted to floats
+enum Nums {
+    Integers(i64, i64),
+    Floats(f64, f64),
 }
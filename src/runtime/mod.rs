@@ -7,6 +7,6 @@ pub use interpreter::*;
 mod callable;
 mod environment;
 mod eq;
-mod error;
+pub mod error;
 mod exit;
 mod interpreter;
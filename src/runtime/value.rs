@@ -1,4 +1,6 @@
-use std::{fmt::Display, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, collections::BTreeMap, fmt::Display, rc::Rc};
+
+use itertools::Itertools;
 
 use crate::token::Kind;
 
@@ -10,25 +12,110 @@ pub const MAX_ARITY: usize = 32;
 pub enum Value {
     String(String),
     Number(f64),
+    Integer(i64),
     Bool(bool),
     Nil,
     Callable(Rc<Callable>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<BTreeMap<Key, Value>>>),
 }
 
-impl From<Value> for String {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Bool(b) => {
-                if b {
-                    "Nah, yeah!".into()
-                } else {
-                    "Yeah, nah!".into()
-                }
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Number(_) => "number",
+            Value::Integer(_) => "integer",
+            Value::Bool(_) => "bool",
+            Value::Nil => "bugger all",
+            Value::Callable(_) => "function",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+        }
+    }
+
+    /// The value as a float if it's a number of either kind
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Integer(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    /// The value as an integer if it's a whole number of either kind that
+    /// fits in one
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Integer(n) => Some(*n),
+            Value::Number(n)
+                if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 =>
+            {
+                Some(*n as i64)
             }
+            _ => None,
+        }
+    }
+
+    /// Formats the value, quoting strings when it's `nested` inside a
+    /// collection so `["1"]` and `[1]` can be told apart. `seen` holds the
+    /// collections being formatted further out, so one that contains itself
+    /// shows up as `[...]` or `{...}` instead of going round forever
+    fn to_string_within(&self, nested: bool, seen: &mut Vec<*const ()>) -> String {
+        match self {
+            Value::Bool(true) => "Nah, yeah!".into(),
+            Value::Bool(false) => "Yeah, nah!".into(),
             Value::Nil => format!("{}", Kind::BuggerAll),
             Value::Number(n) => format!("{}", n),
-            Value::String(s) => s,
+            Value::Integer(n) => format!("{}", n),
+            Value::String(s) if nested => format!("{:?}", s),
+            Value::String(s) => s.clone(),
             Value::Callable(c) => format!("{}", c),
+            Value::List(list) => {
+                let ptr = Rc::as_ptr(list) as *const ();
+                if seen.contains(&ptr) {
+                    return "[...]".into();
+                }
+                seen.push(ptr);
+                let s = format!(
+                    "[{}]",
+                    list.borrow()
+                        .iter()
+                        .map(|v| v.to_string_within(true, seen))
+                        .join(", ")
+                );
+                seen.pop();
+                s
+            }
+            Value::Map(map) => {
+                let ptr = Rc::as_ptr(map) as *const ();
+                if seen.contains(&ptr) {
+                    return "{...}".into();
+                }
+                seen.push(ptr);
+                let s = format!(
+                    "{{{}}}",
+                    map.borrow()
+                        .iter()
+                        .map(|(k, v)| format!(
+                            "{}: {}",
+                            Value::from(k.clone()).to_string_within(true, seen),
+                            v.to_string_within(true, seen)
+                        ))
+                        .join(", ")
+                );
+                seen.pop();
+                s
+            }
+        }
+    }
+}
+
+impl From<Value> for String {
+    fn from(val: Value) -> Self {
+        match val {
+            Value::String(s) => s,
+            other => other.to_string_within(false, &mut Vec::new()),
         }
     }
 }
@@ -53,34 +140,120 @@ impl From<String> for Value {
     }
 }
 
+impl From<Vec<Value>> for Value {
+    fn from(list: Vec<Value>) -> Self {
+        Value::List(Rc::new(RefCell::new(list)))
+    }
+}
+
+impl From<BTreeMap<Key, Value>> for Value {
+    fn from(map: BTreeMap<Key, Value>) -> Self {
+        Value::Map(Rc::new(RefCell::new(map)))
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Self {
         Value::Bool(b)
     }
 }
 
-// impl From<f64> for Value {
-//     fn from(b: f64) -> Self {
-//         Value::Number(b)
-//     }
-// }
+/// A value that can be used as a key in a map. Only strings, numbers and
+/// bools can be keys, so keys are ordered and never change underneath the map
+#[derive(Clone, Debug)]
+pub enum Key {
+    Bool(bool),
+    Number(f64),
+    Integer(i64),
+    String(String),
+}
 
-impl<T: Into<f64> + Numeric> From<T> for Value {
-    fn from(num: T) -> Self {
+impl Key {
+    pub fn from_value(val: &Value) -> Option<Key> {
+        match val {
+            Value::Bool(b) => Some(Key::Bool(*b)),
+            // Whole floats are stored as integers so `1` and `1.0` are the same key
+            Value::Number(n)
+                if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 =>
+            {
+                Some(Key::Integer(*n as i64))
+            }
+            // Adding zero turns -0 into 0 so they're the same key
+            Value::Number(n) => Some(Key::Number(n + 0f64)),
+            Value::Integer(n) => Some(Key::Integer(*n)),
+            Value::String(s) => Some(Key::String(s.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl From<Key> for Value {
+    fn from(key: Key) -> Self {
+        match key {
+            Key::Bool(b) => Value::Bool(b),
+            Key::Number(n) => Value::Number(n),
+            Key::Integer(n) => Value::Integer(n),
+            Key::String(s) => Value::String(s),
+        }
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Key::Bool(a), Key::Bool(b)) => a.cmp(b),
+            (Key::Number(a), Key::Number(b)) => a.total_cmp(b),
+            (Key::Integer(a), Key::Integer(b)) => a.cmp(b),
+            // Never equal since whole numbers are always integer keys
+            (Key::Integer(a), Key::Number(b)) => (*a as f64).total_cmp(b).then(Ordering::Less),
+            (Key::Number(a), Key::Integer(b)) => a.total_cmp(&(*b as f64)).then(Ordering::Greater),
+            (Key::String(a), Key::String(b)) => a.cmp(b),
+            (Key::Bool(_), _) => Ordering::Less,
+            (Key::String(_), _) => Ordering::Greater,
+            (Key::Number(_) | Key::Integer(_), Key::Bool(_)) => Ordering::Greater,
+            (Key::Number(_) | Key::Integer(_), Key::String(_)) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl From<f64> for Value {
+    fn from(num: f64) -> Self {
+        Value::Number(num)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(num: f32) -> Self {
         Value::Number(num.into())
     }
 }
 
-pub trait Numeric {}
-impl Numeric for f64 {}
-impl Numeric for f32 {}
-impl Numeric for i64 {}
-impl Numeric for i32 {}
-impl Numeric for i16 {}
-impl Numeric for i8 {}
-impl Numeric for isize {}
-impl Numeric for u64 {}
-impl Numeric for u32 {}
-impl Numeric for u16 {}
-impl Numeric for u8 {}
-impl Numeric for usize {}
+impl<T: Into<i64> + Integral> From<T> for Value {
+    fn from(num: T) -> Self {
+        Value::Integer(num.into())
+    }
+}
+
+/// Whole number types that always fit in an `i64`
+pub trait Integral {}
+impl Integral for i64 {}
+impl Integral for i32 {}
+impl Integral for i16 {}
+impl Integral for i8 {}
+impl Integral for u32 {}
+impl Integral for u16 {}
+impl Integral for u8 {}
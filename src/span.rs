@@ -0,0 +1,35 @@
+/// A point in the source, as the lexer read it. For upside down programs
+/// that's the flipped text, not what was written
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    /// Bytes from the start of the source
+    pub offset: usize,
+    pub line: usize,
+    /// Characters from the start of the line, counting from 1
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(offset: usize, line: usize, col: usize) -> Self {
+        Self { offset, line, col }
+    }
+}
+
+/// The stretch of source something came from, from `start` up to but not
+/// including `end`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// A span from the start of this one to the end of `other`
+    pub fn to(self, other: Span) -> Self {
+        Self::new(self.start, other.end)
+    }
+}
@@ -1,14 +1,26 @@
 use std::fmt::Display;
 
-#[derive(Clone, Debug, PartialEq)]
+use crate::span::Span;
+
+#[derive(Clone, Debug)]
 pub struct Token {
     pub kind: Kind,
     line: usize,
+    span: Span,
 }
 
 impl Token {
     pub fn new(kind: Kind, line: usize) -> Self {
-        Self { kind, line }
+        Self {
+            kind,
+            line,
+            span: Span::default(),
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
     }
 
     pub fn kind(&self) -> Kind {
@@ -18,6 +30,18 @@ impl Token {
     pub fn line(&self) -> usize {
         self.line
     }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Tokens are the same if they're the same kind on the same line, wherever
+/// on that line they are
+impl PartialEq for Token {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.line == other.line
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -30,6 +54,9 @@ pub enum Kind {
     RightBoomerang, // >
     LeftBracket,    // [
     RightBracket,   // ]
+    LeftBrace,      // {
+    RightBrace,     // }
+    Colon,          // :
     LeftParen,      // (
     RightParen,     // )
     Assign,         // =
@@ -44,8 +71,20 @@ pub enum Kind {
     GTE,            // >=
     Equals,         // ==
     BangEqual,      // !=
+    PlusEqual,      // +=
+    MinusEqual,     // -=
+    AsteriskEqual,  // *=
+    SlashEqual,     // /=
+    ModuloEqual,    // %=
     And,            // &&
     Or,             // ||
+    Power,          // **
+    FloorDivide,    // ~/
+    BitAnd,         // &
+    BitOr,          // |
+    BitXor,         // ^
+    ShiftLeft,      // <<
+    ShiftRight,     // >>
     GoodOnYa,       // GOOD ON YA
     PullYaHeadIn,   // PullYaHeadIn
 
@@ -53,9 +92,12 @@ pub enum Kind {
     Import,       // IMPOHT ME FUNC
     FuckinPiker,  // FUCKINPIKER (early exit)
     MateFuckThis, // mate fuck this (break)
+    ShellBeRight, // she'll be right (continue)
     Until,        // until
     From,         // from
     To,           // to
+    Through,      // through
+    Hoppin,       // hoppin (for loop step)
     Gimme,        // gimme
     Is,           // (is)
     Isa,          // (is a)
@@ -70,6 +112,9 @@ pub enum Kind {
     YaReckon,     // Ya reckon (analogous to if)
     HardYakkaFor, // Hard yakka for (function decl)
     Bail,         // bail (return)
+    GiveItABurl,  // give it a burl (try)
+    Strewth,      // strewth (catch)
+    ChuckAWobbly, // chuck a wobbly (throw)
     True,         // true
     False,        // false
     OiMate,       // OI MATE! (start of block comment)
@@ -80,12 +125,23 @@ pub enum Kind {
     Yeah,
     Nah,
 
-    Ident(String),  // Identifier
-    Number(f64),    // Number literal
-    String(String), // String literal
+    Ident(String),                       // Identifier
+    Number(f64),                         // Number literal
+    Integer(i64),                        // Integer literal
+    String(String),                      // String literal
+    InterpolatedString(Vec<StringPart>), // String literal with ${...} in it
+    Error, // Something that couldn't be lexed, which has already been reported
     EOF,
 }
 
+/// A piece of an interpolated string literal
+#[derive(Clone, PartialEq, Debug)]
+pub enum StringPart {
+    Literal(String),
+    // Tokens of the expression inside `${...}`, ending with EOF
+    Expr(Vec<Token>),
+}
+
 impl Kind {
     pub fn literal(&self) -> String {
         match self {
@@ -97,11 +153,17 @@ impl Kind {
             Kind::FuckinPiker => "fuckinpiker",
             Kind::Modulo => "%",
             Kind::MateFuckThis => "mate fuck this",
+            Kind::ShellBeRight => "she'll be right",
             Kind::LeftBracket => "[",
             Kind::RightBracket => "]",
+            Kind::LeftBrace => "{",
+            Kind::RightBrace => "}",
+            Kind::Colon => ":",
             Kind::Until => "until",
             Kind::From => "from",
             Kind::To => "to",
+            Kind::Through => "through",
+            Kind::Hoppin => "hoppin",
             Kind::Is => "is",
             Kind::Isa => "is a",
             Kind::Tilde => "~",
@@ -113,6 +175,18 @@ impl Kind {
             Kind::Assign => "=",
             Kind::Comma => ",",
             Kind::Plus => "+",
+            Kind::PlusEqual => "+=",
+            Kind::MinusEqual => "-=",
+            Kind::AsteriskEqual => "*=",
+            Kind::SlashEqual => "/=",
+            Kind::ModuloEqual => "%=",
+            Kind::Power => "**",
+            Kind::FloorDivide => "~/",
+            Kind::BitAnd => "&",
+            Kind::BitOr => "|",
+            Kind::BitXor => "^",
+            Kind::ShiftLeft => "<<",
+            Kind::ShiftRight => ">>",
             Kind::Minus => "-",
             Kind::Asterisk => "*",
             Kind::Slash => "/",
@@ -136,13 +210,19 @@ impl Kind {
             Kind::YaReckon => "ya reckon",  // Ya reckon (analogous to if)
             Kind::HardYakkaFor => "the hard yakka for", // Hard yakka for (function decl)
             Kind::Bail => "bail",           // bail (return)
+            Kind::GiveItABurl => "give it a burl", // give it a burl (try)
+            Kind::Strewth => "strewth",     // strewth (catch)
+            Kind::ChuckAWobbly => "chuck a wobbly", // chuck a wobbly (throw)
             Kind::True => "nah, yeah!",     // true
             Kind::False => "yeah, nah!",    // false
             Kind::Nah => "nah",             // true
             Kind::Yeah => "yeah",           // false
             Kind::Ident(ref s) => s.as_str(), // Identifier
             Kind::Number(n) => return format!("{}", n), // Number literal
+            Kind::Integer(n) => return format!("{}", n), // Integer literal
             Kind::String(ref s) => s.as_str(), // String literal
+            Kind::InterpolatedString(_) => "interpolated string",
+            Kind::Error => "error",
             Kind::EOF => "EOF",
         }
         .into()
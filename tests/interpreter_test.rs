@@ -1,10 +1,18 @@
 use aussie_plus_plus::{
+    diagnostic::{Diagnostic, Renderer},
+    interpret, interpret_file,
     lexer::{source, Lexer},
     parser::parser::Parser,
+    repl::{Repl, Status},
     resolver::Resolver,
     runtime::Interpreter,
 };
 
+use std::{
+    env, fs, process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
 enum FallibleKind {
     None,
     Resolver,
@@ -12,6 +20,15 @@ enum FallibleKind {
 }
 
 fn test(src: &str, expected: &str, fallible_kind: FallibleKind) {
+    test_with_resolver(src, expected, fallible_kind, Resolver::new())
+}
+
+fn test_with_resolver(
+    src: &str,
+    expected: &str,
+    fallible_kind: FallibleKind,
+    mut resolver: Resolver,
+) {
     let expected = if expected.is_empty() {
         expected.to_owned()
     } else {
@@ -20,17 +37,17 @@ fn test(src: &str, expected: &str, fallible_kind: FallibleKind) {
     let mut s = "G'DAY MATE! ".to_string();
     s.push_str(src);
     let mut lex = Lexer::new(source::Regular::new(s.chars()));
-    let (tokens, failed) = lex.lex();
+    let (tokens, diagnostics) = lex.lex();
 
-    if failed {
-        panic!("Lexing failed");
+    if !diagnostics.is_empty() {
+        panic!("Lexing failed: {:?}", diagnostics);
     }
 
     println!("Tokens: {:#?}", tokens);
     let mut parser = Parser::new(tokens);
     let mut stmts = parser.parse().unwrap();
 
-    if Resolver::new().resolve(&mut stmts) {
+    if resolver.resolve(&mut stmts).is_err() {
         if !matches!(fallible_kind, FallibleKind::Resolver) {
             panic!("Resolver failed")
         } else {
@@ -41,13 +58,12 @@ fn test(src: &str, expected: &str, fallible_kind: FallibleKind) {
     let mut buf: Vec<u8> = Vec::with_capacity(128);
     let mut iptr = Interpreter::new_with_writer(&mut buf);
 
-    if let Err(e) = iptr.interpret(stmts) {
+    if let Err(diagnostics) = iptr.interpret(stmts) {
         if !matches!(fallible_kind, FallibleKind::Interpreter) {
-            panic!("Failed to interpret: {}", e);
-        } else {
-            return;
+            panic!("Failed to interpret: {:?}", diagnostics);
         }
     }
+    drop(iptr);
 
     println!("Testing expression: {}", s);
     match std::str::from_utf8(&buf) {
@@ -62,6 +78,46 @@ fn test_code(src: &str, expected: &str) {
     test(src, expected, FallibleKind::None)
 }
 
+/// Write `files` to a fresh directory and run the first one
+fn test_files(files: &[(&str, &str)], expected: &str, fallible_kind: FallibleKind) {
+    static DIRS: AtomicUsize = AtomicUsize::new(0);
+    let dir = env::temp_dir().join(format!(
+        "aussie_test_{}_{}",
+        process::id(),
+        DIRS.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir_all(&dir).unwrap();
+
+    for (name, src) in files {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("G'DAY MATE! {}", src)).unwrap();
+    }
+
+    let (main, src) = files[0];
+    let resolver = Resolver::for_file(&dir.join(main));
+    test_with_resolver(src, expected, fallible_kind, resolver);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// Run `lines` one at a time through a single REPL session
+fn test_repl(lines: &[&str], expected: &str) {
+    let mut buf: Vec<u8> = Vec::with_capacity(128);
+    let mut repl = Repl::new_with_writer(&mut buf);
+
+    for (i, line) in lines.iter().enumerate() {
+        if i == 0 {
+            repl.feed(&format!("G'DAY MATE! {}", line));
+        } else {
+            repl.feed(line);
+        }
+    }
+
+    drop(repl);
+    assert_eq!(std::str::from_utf8(&buf).unwrap(), expected);
+}
+
 #[test]
 fn test_constants() {
     test(
@@ -72,6 +128,369 @@ fn test_constants() {
         "420",
         FallibleKind::Resolver,
     );
+
+    // Inner scopes can still shadow a constant
+    test_code(
+        "I FULLY RECKON x = 1;
+        < I RECKON x = 2; x = 3; GIMME x; >
+        GIMME x;",
+        "3\n1",
+    );
+
+    // Reading a constant never changes it
+    test_code(
+        "I FULLY RECKON x = 5;
+        GIMME -x;
+        GIMME !x;
+        GIMME x;",
+        "-5\nYeah, nah!\n5",
+    );
+
+    // A function can't sneak past the resolver to change a variable that
+    // was made a constant after the function was declared
+    fn session(lines: &[&str]) -> (String, Vec<(&'static str, Option<usize>)>) {
+        let mut buf: Vec<u8> = Vec::new();
+        let mut iptr = Interpreter::new_with_writer(&mut buf);
+        let mut resolver = Resolver::for_repl();
+        let mut diagnostics = Vec::new();
+        for line in lines {
+            let src = format!("G'DAY MATE! {}", line);
+            let mut lexer = Lexer::new(source::Regular::new(src.chars()));
+            let (tokens, _) = lexer.lex();
+            let mut stmts = Parser::new(tokens).parse().unwrap();
+            resolver.resolve(&mut stmts).unwrap();
+            diagnostics.extend(iptr.interpret(stmts).err().unwrap_or_default());
+        }
+        drop(iptr);
+
+        let codes = diagnostics.iter().map(|d| (d.code, d.line())).collect();
+        (String::from_utf8(buf).unwrap(), codes)
+    }
+
+    assert_eq!(
+        session(&[
+            "I RECKON x = 1;",
+            "THE HARD YAKKA FOR change IS () < x = 2; >",
+            "THE HARD YAKKA FOR bump IS () < GOOD ON YA x; >",
+            "I FULLY RECKON x = 3;",
+            "change();",
+            "bump();",
+            "GIMME x;",
+        ]),
+        ("3\n".to_owned(), vec![("E019", Some(1)), ("E019", Some(1))])
+    );
+
+    // It's only the variable that's constant, what's in a list can change
+    assert_eq!(
+        session(&[
+            "I RECKON xs = [1];",
+            "THE HARD YAKKA FOR change IS () < xs[0] = 2; >",
+            "I FULLY RECKON xs = [5];",
+            "change();",
+            "GIMME xs;",
+        ]),
+        ("[2]\n".to_owned(), vec![])
+    );
+}
+
+#[test]
+fn test_repl_session() {
+    // Variables carry over between lines, and can be redeclared
+    test_repl(
+        &[
+            "I RECKON x = 1;",
+            "x = x + 1;",
+            "GIMME x;",
+            "I RECKON x = 5;",
+            "GIMME x;",
+        ],
+        "2\n5\n",
+    );
+
+    // Constants can't be reassigned or redeclared on later lines, and
+    // what's wrong goes to the writer too
+    test_repl(
+        &[
+            "I FULLY RECKON x = 420;",
+            "x = 69;",
+            "I RECKON x = 69;",
+            "THE HARD YAKKA FOR sneaky IS () < x = 69; >",
+            "GIMME x;",
+        ],
+        "error[R011]: x: OI, YA CAN'T REDEFINE THIS!
+  |
+1 | x = 69;
+  | ^
+error[R006]: x: WAKE UP FUCK-WIT! A VARIABLE WITH THAT NAME ALREADY EXISTS IN THIS SCOPE.
+  |
+1 | I RECKON x = 69;
+  |          ^
+error[R011]: x: OI, YA CAN'T REDEFINE THIS!
+  |
+1 | THE HARD YAKKA FOR sneaky IS () < x = 69; >
+  |                                   ^
+420
+",
+    );
+
+    // A line that fails to resolve doesn't leave its declarations behind
+    test_repl(
+        &[
+            "I RECKON x = 1; GIMME nope;",
+            "GIMME x;",
+            "I RECKON x = 3; GIMME x;",
+        ],
+        "error[R008]: nope: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!
+  |
+1 | G'DAY MATE! I RECKON x = 1; GIMME nope;
+  |                                   ^^^^
+error[R008]: x: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!
+  |
+1 | GIMME x;
+  |       ^
+3
+",
+    );
+}
+
+#[test]
+fn test_repl_entries() {
+    // A trailing expression has its value echoed, unless it's nil or an assignment
+    test_repl(
+        &[
+            "1 + 2;",
+            "I RECKON x = 5;",
+            "x;",
+            "x = 6;",
+            "THE HARD YAKKA FOR nothing IS () < >",
+            "nothing();",
+        ],
+        "3\n5\n",
+    );
+
+    // Unfinished blocks and raw strings carry on over the next lines
+    test_repl(
+        &[
+            "THE HARD YAKKA FOR double IS ( x ) <",
+            "    BAIL x * 2;",
+            ">",
+            "double(4);",
+            "GIMME \"\"\"one",
+            "two\"\"\";",
+        ],
+        "8\none\ntwo\n",
+    );
+
+    let mut repl = Repl::new();
+    assert_eq!(repl.feed("G'DAY MATE!"), Status::Ready);
+    assert_eq!(
+        repl.feed("THE HARD YAKKA FOR f IS () <"),
+        Status::Unfinished
+    );
+    assert!(repl.is_unfinished());
+    repl.cancel();
+    assert!(!repl.is_unfinished());
+    assert_eq!(repl.feed("GIMME 1 + ;"), Status::Ready);
+    assert_eq!(repl.feed(":quit"), Status::Quit);
+    assert_eq!(repl.feed("CHEERS C***!"), Status::Quit);
+}
+
+#[test]
+fn test_repl_commands() {
+    test_repl(
+        &["I RECKON b = 2;", "I RECKON a = \"one\";", ":env"],
+        "a = one\nb = 2\n",
+    );
+
+    // Resetting forgets variables, constants included, and starts a new
+    // program that needs its own G'DAY MATE!
+    test_repl(
+        &[
+            "I FULLY RECKON a = 1;",
+            ":reset",
+            "G'DAY MATE!",
+            "I FULLY RECKON a = 2;",
+            "GIMME a;",
+        ],
+        "2\n",
+    );
+    test_repl(
+        &["GIMME 1;", ":reset", "GIMME 2;", ":nope", ":load"],
+        "1\nerror[P010]: YA DAFT BUGGER! YA DIDN'T WRITE \"G'DAY MATE!\" TO START PROGRAM!!
+WHAT'S :nope MEANT TO BE? TRY :help
+OI! GIVE US A FILE TO LOAD, YA GALAH
+",
+    );
+
+    let path = env::temp_dir().join(format!("aussie_load_{}.aussie", process::id()));
+    fs::write(
+        &path,
+        "G'DAY MATE!\nI FULLY RECKON loaded = 42;\nGIMME \"LOADED\";\nCHEERS C***!",
+    )
+    .unwrap();
+    let load = format!(":load {}", path.display());
+    test_repl(
+        &["I RECKON x = 1;", &load, "GIMME loaded + x;"],
+        "LOADED\n43\n",
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_diagnostics() {
+    fn codes(result: Result<(), Vec<Diagnostic>>) -> Vec<(&'static str, Option<usize>)> {
+        let diagnostics = result.unwrap_err();
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        diagnostics.iter().map(|d| (d.code, d.line())).collect()
+    }
+
+    assert_eq!(
+        codes(interpret("G'DAY MATE! GIMME \"oops")),
+        [("L007", Some(1))]
+    );
+    assert_eq!(codes(interpret("GIMME 1;")), [("P010", None)]);
+    assert_eq!(
+        codes(interpret("G'DAY MATE! GIMME 1 +;")),
+        [("P009", Some(1))]
+    );
+    assert_eq!(
+        codes(interpret("G'DAY MATE!\nGIMME nope;")),
+        [("R008", Some(2))]
+    );
+
+    // The line is kept apart from the message, and only shown in front of
+    // it when there's no source to point at
+    let diagnostics = interpret("G'DAY MATE!\nGIMME nope;").unwrap_err();
+    assert_eq!(
+        diagnostics[0].message,
+        "nope: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!"
+    );
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "[line 2] nope: CAAARN! THAT VAR ISN'T DEFINED YA DAFT BUGGER!"
+    );
+
+    // Lexing and parsing both report everything, without piling on more
+    // errors for what the lexer couldn't make sense of
+    assert_eq!(
+        codes(interpret(
+            "G'DAY MATE!\nGIMME \"\\q\";\nGIMME @;\nGIMME 1 +;"
+        )),
+        [("L015", Some(2)), ("L006", Some(3)), ("P009", Some(4))]
+    );
+    assert_eq!(
+        codes(interpret(
+            "G'DAY MATE!\nGIMME 1 +;\nGIMME \"a ${@} b\";\n<\n GIMME 1 > @;\n>"
+        )),
+        [("P009", Some(2)), ("L006", Some(3)), ("L006", Some(5))]
+    );
+    assert_eq!(
+        codes(interpret("G'DAY MATE!\nGIMME YEAH;\nGIMME 1 + 2;")),
+        [("L002", Some(2))]
+    );
+
+    // Runtime errors don't stop the statements after them
+    assert_eq!(
+        codes(interpret("G'DAY MATE!\nGIMME 1 / 0;\nGIMME [1][5];")),
+        [("E017", Some(2)), ("E010", Some(3))]
+    );
+    assert_eq!(
+        codes(interpret(
+            "G'DAY MATE!\nIMPOHT ME FUNC GotAny;\nGotAny(5, 1);\nGotAny({}, [1]);"
+        )),
+        [("E020", Some(3)), ("E013", Some(4))]
+    );
+    assert_eq!(
+        codes(interpret(
+            "G'DAY MATE!\nIMPOHT ME FUNC HowLongIsAPieceOfString;\nHowLongIsAPieceOfString(5);"
+        )),
+        [("E022", Some(3))]
+    );
+
+    // Problems in an imported file come back noting which file they're in
+    let dir = env::temp_dir().join(format!("aussie_diagnostics_{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("mate.aussie"), "G'DAY MATE!\nGIMME nope;").unwrap();
+    fs::write(
+        dir.join("main.aussie"),
+        "G'DAY MATE!\nIMPOHT ME FUNC \"mate.aussie\";",
+    )
+    .unwrap();
+    let diagnostics = interpret_file(&dir.join("main.aussie")).unwrap_err();
+    assert_eq!(
+        diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(),
+        ["R008", "R005"]
+    );
+    assert!(diagnostics[0].notes[0].ends_with("mate.aussie"));
+
+    // A clash with something imported points at the import, since that's
+    // in the file being shown
+    fs::write(
+        dir.join("mate.aussie"),
+        "G'DAY MATE!\n\n\nI FULLY RECKON x = 1;",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("main.aussie"),
+        "G'DAY MATE!\nI RECKON x = 2;\nIMPOHT ME FUNC \"mate.aussie\";",
+    )
+    .unwrap();
+    let diagnostics = interpret_file(&dir.join("main.aussie")).unwrap_err();
+    assert_eq!(
+        diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(),
+        ["R006"]
+    );
+    let span = diagnostics[0].span.unwrap();
+    assert_eq!((span.start.line, span.start.col), (3, 16));
+    assert_eq!(diagnostics[0].labels[0].span.start.line, 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_render_diagnostics() {
+    fn render(src: &str, colour: bool) -> Vec<String> {
+        let renderer = Renderer::new(src).with_colour(colour);
+        let diagnostics = interpret(src).unwrap_err();
+        diagnostics.iter().map(|d| renderer.render(d)).collect()
+    }
+
+    assert_eq!(
+        render("G'DAY MATE!\nGIMME 1 +;", false),
+        ["error[P009]: EXPECTED NUMBER, STRING, BOOLEAN, NIL, LIST, MAP, OR IDENTIFIER BUT GOT ';'
+  |
+2 | GIMME 1 +;
+  |          ^"]
+    );
+
+    // Secondary labels point out where the constant came from
+    assert_eq!(
+        render("G'DAY MATE!\nI FULLY RECKON x = 1;\n\tx = 2;", false),
+        ["error[R011]: x: OI, YA CAN'T REDEFINE THIS!
+  |
+2 | I FULLY RECKON x = 1;
+  |                - CONSTANT DECLARED HERE
+3 | \tx = 2;
+  | \t^"]
+    );
+
+    // Runtime errors point at the expression that failed
+    assert_eq!(
+        render("G'DAY MATE!\nGIMME 1 + 1 / 0;", false),
+        ["error[E017]: YA CAN'T DIVIDE BY ZERO, YA DRONGO!
+  |
+2 | GIMME 1 + 1 / 0;
+  |           ^^^^^"]
+    );
+
+    // Without a span the header is all there is, so it says the line
+    assert_eq!(
+        render("G'DAY MATE!\nGIMME 1;\nMATE FUCK THIS;", false),
+        ["error[E002]: [line 3] INVALID BREAK, FIX IT FUCKWIT."]
+    );
+
+    let coloured = render("G'DAY MATE! GIMME nope;", true);
+    assert!(coloured[0].starts_with("\x1b[1;31merror[R008]\x1b[0m"));
+    assert!(coloured[0].contains("\x1b[1;31m^^^^\x1b[0m"));
 }
 
 #[test]
@@ -88,6 +507,137 @@ fn test_imports() {
 ",
         "",
     );
+
+    // Whole floats count as whole numbers, and an empty range gives its start
+    test_code(
+        "IMPOHT ME FUNC ChuckSomeDice;
+        gimme ChuckSomeDice(3.0, 4.0);
+        gimme ChuckSomeDice(5, 5);
+        give it a burl <
+            ChuckSomeDice(0, 1.5);
+        > strewth err <
+            gimme err[\"message\"];
+        >",
+        "3\n5\nOI MATE! ChuckSomeDice ONLY WORKS ON WHOLE NUMBERS, NOT 1.5",
+    );
+}
+
+#[test]
+fn test_import_modules() {
+    test_files(
+        &[
+            (
+                "main.aussie",
+                "
+    IMPOHT ME FUNC \"lib/maths.aussie\";
+    GIMME double(TWO);
+    ",
+            ),
+            (
+                "lib/maths.aussie",
+                "
+    I FULLY RECKON TWO = 2;
+    I RECKON hidden = 3;
+    THE HARD YAKKA FOR double IS ( x ) <
+        BAIL x * TWO;
+    >
+    ",
+            ),
+        ],
+        "4",
+        FallibleKind::None,
+    );
+
+    // Modules are only run once, and their imports are relative to themselves
+    test_files(
+        &[
+            (
+                "main.aussie",
+                "
+    IMPOHT ME FUNC \"a/one.aussie\";
+    IMPOHT ME FUNC \"a/two.aussie\";
+    GIMME ONE + TWO;
+    ",
+            ),
+            (
+                "a/one.aussie",
+                "
+    IMPOHT ME FUNC \"shared.aussie\";
+    I FULLY RECKON ONE = SHARED;
+    ",
+            ),
+            (
+                "a/two.aussie",
+                "
+    IMPOHT ME FUNC \"shared.aussie\";
+    I FULLY RECKON TWO = SHARED + 1;
+    ",
+            ),
+            (
+                "a/shared.aussie",
+                "
+    GIMME \"loading shared\";
+    I FULLY RECKON SHARED = 1;
+    ",
+            ),
+        ],
+        "loading shared\n3",
+        FallibleKind::None,
+    );
+
+    // Importing the same module twice in one file is harmless
+    test_files(
+        &[
+            (
+                "main.aussie",
+                "
+    IMPOHT ME FUNC \"shared.aussie\";
+    IMPOHT ME FUNC \"./shared.aussie\";
+    GIMME SHARED;
+    ",
+            ),
+            (
+                "shared.aussie",
+                "
+    GIMME \"loading shared\";
+    I FULLY RECKON SHARED = 1;
+    ",
+            ),
+        ],
+        "loading shared\n1",
+        FallibleKind::None,
+    );
+
+    test_files(
+        &[
+            (
+                "main.aussie",
+                "IMPOHT ME FUNC \"maths.aussie\"; GIMME hidden;",
+            ),
+            ("maths.aussie", "I RECKON hidden = 3;"),
+        ],
+        "",
+        FallibleKind::Resolver,
+    );
+    test_files(
+        &[
+            ("main.aussie", "IMPOHT ME FUNC \"a.aussie\";"),
+            ("a.aussie", "IMPOHT ME FUNC \"b.aussie\";"),
+            ("b.aussie", "IMPOHT ME FUNC \"a.aussie\";"),
+        ],
+        "",
+        FallibleKind::Resolver,
+    );
+    test_files(
+        &[("main.aussie", "IMPOHT ME FUNC \"main.aussie\";")],
+        "",
+        FallibleKind::Resolver,
+    );
+    test_files(
+        &[("main.aussie", "IMPOHT ME FUNC \"nowhere.aussie\";")],
+        "",
+        FallibleKind::Resolver,
+    );
 }
 
 #[test]
@@ -159,156 +709,451 @@ fn test_functions() {
 }
 
 #[test]
-fn test_break() {
+fn test_lambdas() {
     test_code(
         "
-    I RECKON x IS A walkabout FROM [1 to 5] <
-        YA RECKON x == 2 ? MATE FUCK THIS;
-        GIMME \"iteration number: \" + x;
+    THE HARD YAKKA FOR twice IS ( f, x ) <
+        BAIL f(f(x));
     >
+    GIMME twice(THE HARD YAKKA FOR ( x ) < BAIL x * 3; >, 2);
     ",
-        "iteration number: 1",
+        "18",
     );
-}
 
-#[test]
-fn test_for_loop_ranges() {
     test_code(
         "
-    I reckon x is a walkabout from [0 to 2] <
-        gimme x;
+    THE HARD YAKKA FOR counter IS () <
+        I RECKON count = 0;
+        BAIL THE HARD YAKKA FOR () <
+            count = count + 1;
+            BAIL count;
+        >;
     >
+    I RECKON next = counter();
+    next();
+    GIMME next();
     ",
-        "0\n1\n2",
+        "2",
     );
 
     test_code(
         "
-    I reckon x is a walkabout from (0 to 2] <
-        gimme x;
-    >
+    THE HARD YAKKA FOR () < GIMME \"straight away\"; >();
     ",
-        "1\n2",
+        "straight away",
+    );
+
+    test(
+        "
+    I RECKON f = THE HARD YAKKA FOR () < BAIL nope; >;
+    ",
+        "",
+        FallibleKind::Resolver,
     );
+}
 
+#[test]
+fn test_try_catch() {
     test_code(
         "
-    I reckon x is a walkabout from [0 to 2] <
-        gimme x;
+    GIVE IT A BURL <
+        CHUCK A WOBBLY \"stubbed me toe\";
+        GIMME \"never gets here\";
+    > STREWTH err <
+        GIMME \"caught: \" + err;
     >
     ",
-        "0\n1\n2",
+        "caught: stubbed me toe",
     );
 
     test_code(
         "
-    I reckon x is a walkabout from [0 to 2) <
-        gimme x;
+    I RECKON x = 1;
+    THE HARD YAKKA FOR deep IS ( n ) <
+        I RECKON x = n;
+        YA RECKON n == 0 ? CHUCK A WOBBLY [x];
+        BAIL deep(n - 1);
+    >
+    GIVE IT A BURL <
+        deep(3);
+    > STREWTH err <
+        GIMME err;
     >
+    GIMME x;
     ",
-        "0\n1",
+        "[0]\n1",
     );
 
     test_code(
         "
-    I reckon x is a walkabout from (0 to 0) <
-        gimme \"val: \" + x;
+    GIVE IT A BURL <
+        GIMME [1, 2][5];
+    > STREWTH err <
+        GIMME err[\"line\"];
+        GIMME err[\"message\"];
     >
     ",
-        "",
+        "3\nSTREWTH! INDEX 5 IS OUT OF BOUNDS, IT'S ONLY GOT 2 ITEMS",
     );
 
     test_code(
         "
-    I reckon x is a walkabout from (0 to 0] <
-        gimme \"val: \" + x;
+    GIVE IT A BURL <
+        GIVE IT A BURL <
+            CHUCK A WOBBLY 1;
+        > STREWTH err <
+            CHUCK A WOBBLY err + 1;
+        >
+    > STREWTH err <
+        GIMME err;
     >
     ",
-        "",
+        "2",
     );
 
-    test_code(
+    test(
         "
-    I reckon x is a walkabout from (-1 to 1] <
-        gimme x;
+    CHUCK A WOBBLY \"nobody's home\";
+    ",
+        "",
+        FallibleKind::Interpreter,
+    );
+    test(
+        "
+    GIVE IT A BURL <
+        GIMME 1;
+    > STREWTH err <
+        GIMME oops;
     >
     ",
-        "0\n1",
+        "",
+        FallibleKind::Resolver,
     );
+}
 
-    // Variables as ranges
+#[test]
+fn test_break() {
     test_code(
         "
-        I reckon z = -1;
-        I reckon y = 1;
-        I reckon x is a walkabout from (z to y] <
-            gimme x;
-        >
+    I RECKON x IS A walkabout FROM [1 to 5] <
+        YA RECKON x == 2 ? MATE FUCK THIS;
+        GIMME \"iteration number: \" + x;
+    >
     ",
-        "0\n1",
+        "iteration number: 1",
     );
 }
 
 #[test]
-fn test_vars() {
+fn test_continue() {
     test_code(
         "
-    I RECKON x = 10;
-    gimme x;
-    x = 5;
-    gimme x;
+    I RECKON x IS A walkabout FROM [1 to 5] <
+        YA RECKON x % 2 == 0 ? SHE'LL BE RIGHT;
+        GIMME x;
+    >
     ",
-        "10\n5",
+        "1\n3\n5",
     );
 
     test_code(
-        "I RECKON x = 10;
-    <
-        I RECKON x = 5;
-        gimme x;
+        "
+    I RECKON x IS A walkabout THROUGH [1, 2, 3] <
+        YA RECKON x == 2 ? SHE'LL BE RIGHT;
+        GIMME x;
     >
-    gimme x;
     ",
-        "5\n10",
+        "1\n3",
     );
 
     test_code(
-        "I RECKON x = 10;
-    <
-        x = 5;
-        gimme x;
+        "
+    I RECKON x = 0;
+    I RECKON I'LL HAVE A WALKABOUT UNTIL (x >= 4) <
+        x = x + 1;
+        YA RECKON x == 2 ? SHE'LL BE RIGHT;
+        GIMME x;
     >
-    gimme x;
     ",
-        "5\n5",
+        "1\n3\n4",
+    );
+
+    test("SHE'LL BE RIGHT;", "", FallibleKind::Resolver);
+    test(
+        "
+    I RECKON x IS A walkabout FROM [1 to 5] <
+        THE HARD YAKKA FOR skip IS () <
+            SHE'LL BE RIGHT;
+        >
+    >
+    ",
+        "",
+        FallibleKind::Resolver,
     );
 }
 
 #[test]
-fn test_match() {
-    // Works with bools
+fn test_labelled_loops() {
     test_code(
-        "i reckon x = 2;
-        ya reckon x == 2 is a <
-                    Nah, yeah! ~ gimme \"FARK\";
-                    Yeah, nah! ~ gimme 420;
-                >",
-        "FARK",
+        "
+    outer: I RECKON x IS A walkabout FROM [1 to 3] <
+        I RECKON y IS A walkabout FROM [1 to 3] <
+            YA RECKON y == 2 ? MATE FUCK THIS outer;
+            GIMME \"\" + x + \",\" + y;
+        >
+    >
+    GIMME \"done\";
+    ",
+        "1,1\ndone",
     );
 
-    // Works with numbers
     test_code(
-        "i reckon x = 420;
-        ya reckon x is a <
-                    1 ~ gimme \"FARK\";
-                    2 ~ gimme \"CARN\";
-                    420 ~ gimme \"FAIR DINKUM\";
-                >
-                gimme x;",
-        "FAIR DINKUM\n420",
-    );
-
-    // Works with strings
+        "
+    outer: I RECKON x IS A walkabout THROUGH [1, 2] <
+        I RECKON y IS A walkabout FROM [1 to 3] <
+            YA RECKON y == 2 ? SHE'LL BE RIGHT outer;
+            GIMME \"\" + x + \",\" + y;
+        >
+    >
+    ",
+        "1,1\n2,1",
+    );
+
+    test_code(
+        "
+    I RECKON n = 0;
+    outer: I RECKON I'LL HAVE A WALKABOUT UNTIL (n >= 3) <
+        n = n + 1;
+        inner: I RECKON y IS A walkabout FROM [1 to 3] <
+            YA RECKON n == 2 ? SHE'LL BE RIGHT outer;
+            YA RECKON y == 2 ? MATE FUCK THIS inner;
+            GIMME \"\" + n + \",\" + y;
+        >
+    >
+    ",
+        "1,1\n3,1",
+    );
+
+    test(
+        "
+    I RECKON x IS A walkabout FROM [1 to 3] <
+        MATE FUCK THIS outer;
+    >
+    ",
+        "",
+        FallibleKind::Resolver,
+    );
+    test(
+        "
+    outer: I RECKON x IS A walkabout FROM [1 to 3] <
+        outer: I RECKON y IS A walkabout FROM [1 to 3] <
+            MATE FUCK THIS outer;
+        >
+    >
+    ",
+        "",
+        FallibleKind::Resolver,
+    );
+    test(
+        "
+    outer: I RECKON x IS A walkabout FROM [1 to 3] <
+        THE HARD YAKKA FOR leg_it IS () <
+            MATE FUCK THIS outer;
+        >
+    >
+    ",
+        "",
+        FallibleKind::Resolver,
+    );
+}
+
+#[test]
+fn test_for_loop_ranges() {
+    test_code(
+        "
+    I reckon x is a walkabout from [0 to 2] <
+        gimme x;
+    >
+    ",
+        "0\n1\n2",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from (0 to 2] <
+        gimme x;
+    >
+    ",
+        "1\n2",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from [0 to 2] <
+        gimme x;
+    >
+    ",
+        "0\n1\n2",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from [0 to 2) <
+        gimme x;
+    >
+    ",
+        "0\n1",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from (0 to 0) <
+        gimme \"val: \" + x;
+    >
+    ",
+        "",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from (0 to 0] <
+        gimme \"val: \" + x;
+    >
+    ",
+        "",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from (-1 to 1] <
+        gimme x;
+    >
+    ",
+        "0\n1",
+    );
+
+    // Variables as ranges
+    test_code(
+        "
+        I reckon z = -1;
+        I reckon y = 1;
+        I reckon x is a walkabout from (z to y] <
+            gimme x;
+        >
+    ",
+        "0\n1",
+    );
+}
+
+#[test]
+fn test_for_loop_steps() {
+    test_code(
+        "
+    I reckon x is a walkabout from [0 to 6] hoppin 2 <
+        gimme x;
+    >
+    ",
+        "0\n2\n4\n6",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from (0 to 6) hoppin 2 <
+        gimme x;
+    >
+    ",
+        "2\n4",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from [1 to 0] hoppin 0.25 <
+        gimme x;
+    >
+    ",
+        "1\n0.75\n0.5\n0.25\n0",
+    );
+
+    test_code(
+        "
+    I reckon x is a walkabout from [0 to 5] hoppin 2 <
+        gimme x;
+    >
+    ",
+        "0\n2\n4",
+    );
+
+    test(
+        "
+    I reckon x is a walkabout from [0 to 5] hoppin 0 <
+        gimme x;
+    >
+    ",
+        "",
+        FallibleKind::Interpreter,
+    );
+}
+
+#[test]
+fn test_vars() {
+    test_code(
+        "
+    I RECKON x = 10;
+    gimme x;
+    x = 5;
+    gimme x;
+    ",
+        "10\n5",
+    );
+
+    test_code(
+        "I RECKON x = 10;
+    <
+        I RECKON x = 5;
+        gimme x;
+    >
+    gimme x;
+    ",
+        "5\n10",
+    );
+
+    test_code(
+        "I RECKON x = 10;
+    <
+        x = 5;
+        gimme x;
+    >
+    gimme x;
+    ",
+        "5\n5",
+    );
+}
+
+#[test]
+fn test_match() {
+    // Works with bools
+    test_code(
+        "i reckon x = 2;
+        ya reckon x == 2 is a <
+                    Nah, yeah! ~ gimme \"FARK\";
+                    Yeah, nah! ~ gimme 420;
+                >",
+        "FARK",
+    );
+
+    // Works with numbers
+    test_code(
+        "i reckon x = 420;
+        ya reckon x is a <
+                    1 ~ gimme \"FARK\";
+                    2 ~ gimme \"CARN\";
+                    420 ~ gimme \"FAIR DINKUM\";
+                >
+                gimme x;",
+        "FAIR DINKUM\n420",
+    );
+
+    // Works with strings
     test_code(
         "i reckon x = \"G'day mate\";
         ya reckon x is a <
@@ -341,6 +1186,128 @@ fn test_match() {
         ",
         "42069",
     );
+
+    // Several patterns in one branch
+    test_code(
+        "i reckon x = \"XXXX\";
+        ya reckon x is a <
+            \"VB\", \"XXXX\", \"Coopers\" ~ gimme \"beer\";
+            somethinElse ~ gimme \"not beer\";
+        >
+        ",
+        "beer",
+    );
+
+    // Ranges
+    test_code(
+        "i reckon x is a walkabout through [-5, 0, 5, 10, 15] <
+            ya reckon x is a <
+                [-10 to 0) ~ gimme \"negative\";
+                [0 to 10) ~ gimme \"small\";
+                10, [100 to 1000] ~ gimme \"big\";
+                somethinElse ~ gimme \"dunno\";
+            >
+        >
+        ",
+        "negative\nsmall\nsmall\nbig\ndunno",
+    );
+
+    // Guards, which can see the binding of a catch-all
+    test_code(
+        "i reckon x is a walkabout through [1, 2, 3, 4] <
+            ya reckon x is a <
+                1 ya reckon x > 5 ~ gimme \"never\";
+                n ya reckon n % 2 == 0 ~ gimme \"even \" + n;
+                n ~ gimme \"odd \" + n;
+            >
+        >
+        ",
+        "odd 1\neven 2\nodd 3\neven 4",
+    );
+
+    test(
+        "ya reckon 1 is a <
+            n ya reckon m > 1 ~ gimme n;
+        >
+        ",
+        "",
+        FallibleKind::Resolver,
+    );
+}
+
+#[test]
+fn test_match_expression() {
+    test_code(
+        "i reckon x is a walkabout through [1, 2, 5, 20] <
+            i reckon size = ya reckon x is a <
+                1 ~ \"one\";
+                [2 to 10) ~ \"a few\";
+                n ~ \"heaps (\" + n + \")\";
+            >;
+            gimme size;
+        >
+        ",
+        "one\na few\na few\nheaps (20)",
+    );
+
+    test_code(
+        "gimme ya reckon 4 is a <
+            n ya reckon n % 2 == 0 ~ n / 2;
+            n ~ n * 3 + 1;
+        > + 1;
+        ",
+        "3",
+    );
+
+    test_code(
+        "give it a burl <
+            gimme ya reckon \"VB\" is a <
+                \"XXXX\" ~ 1;
+            >;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        ",
+        "FAIR DINKUM, NOTHING MATCHED VB AND THERE'S NO CATCH-ALL",
+    );
+}
+
+#[test]
+fn test_interpolation() {
+    test_code(
+        "i reckon name = \"Bazza\";
+        i reckon beers = [1, 2];
+        gimme \"${name} has had ${beers[0] + beers[1]} beers, ${\"mate\"}\";
+        gimme \"${{\"nested\": \"braces\"}[\"nested\"]}\";
+        ",
+        "Bazza has had 3 beers, mate\nbraces",
+    );
+
+    // Errors inside an embedded expression point at its line
+    test_code(
+        "give it a burl <
+            gimme \"first line
+            ${[1][5]}\";
+        > strewth err <
+            gimme err[\"line\"];
+        >
+        ",
+        "3",
+    );
+
+    test("gimme \"${nope}\";", "", FallibleKind::Resolver);
+}
+
+#[test]
+fn test_string_escapes() {
+    test_code(
+        r#"gimme "she said \"g'day\"\tto ${"\"Bazza\""}";
+        gimme "costs \${price}, \\o/ \u{1F998}";
+        gimme """raw ${not} \n "quotes"
+and lines""";
+        "#,
+        "she said \"g'day\"\tto \"Bazza\"\ncosts ${price}, \\o/ \u{1F998}\nraw ${not} \\n \"quotes\"\nand lines",
+    );
 }
 
 #[test]
@@ -433,3 +1400,398 @@ fn test_ops() {
 
     test_code("gimme 5 + 5 * 2 / 2;", "10");
 }
+
+#[test]
+fn test_integers() {
+    // Integers don't lose precision the way floats do
+    test_code(
+        "i reckon id = 9007199254740993;
+        gimme id;
+        gimme id == 9007199254740992;
+        gimme id - 1 == 9007199254740992;",
+        "9007199254740993\nYeah, nah!\nNah, yeah!",
+    );
+
+    // Integers and floats are only equal if they're exactly the same number
+    test_code(
+        "gimme 9007199254740993 == 9007199254740992.0;
+        gimme 9007199254740992 == 9007199254740992.0;
+        gimme 9223372036854775807 == 9223372036854775807.0;",
+        "Yeah, nah!\nNah, yeah!\nYeah, nah!",
+    );
+
+    // Mixing in a float promotes to float
+    test_code(
+        "gimme 1 + 0.5;
+        gimme 6 / 3;
+        gimme 6 / 3 == 2;
+        gimme 7 / 2;
+        gimme -7 % 3;
+        gimme 7.5 % 2;
+        gimme 1 == 1.0;
+        gimme 2 < 2.5;",
+        "1.5\n2\nNah, yeah!\n3.5\n-1\n1.5\nNah, yeah!\nNah, yeah!",
+    );
+
+    // Whole floats and integers are the same map key
+    test_code(
+        "i reckon m = {1: \"one\"};
+        gimme m[1.0];
+        m[2.0] = \"two\";
+        gimme m;",
+        "one\n{1: \"one\", 2: \"two\"}",
+    );
+
+    test_code(
+        "i reckon x is a walkabout from [9007199254740993 to 9007199254740995) <
+            gimme x;
+        >
+        i reckon y is a walkabout from [9223372036854775806 to 9223372036854775807] <
+            gimme y;
+        >",
+        "9007199254740993\n9007199254740994\n9223372036854775806\n9223372036854775807",
+    );
+
+    test_code(
+        "give it a burl <
+            gimme 9223372036854775807 + 1;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme -(-9223372036854775807 - 1);
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme 5 % 0;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        i reckon min = -9223372036854775807 - 1;
+        give it a burl <
+            gimme min ~/ -1;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        gimme min % -1;",
+        "STONE THE CROWS! 9223372036854775807 + 1 IS TOO BIG FOR AN INTEGER
+STONE THE CROWS! -(-9223372036854775808) IS TOO BIG FOR AN INTEGER
+YA CAN'T DIVIDE BY ZERO, YA DRONGO!
+STONE THE CROWS! -9223372036854775808 ~/ -1 IS TOO BIG FOR AN INTEGER
+0",
+    );
+}
+
+#[test]
+fn test_power_floor_and_bitwise() {
+    test_code(
+        "gimme 2 ** 10;
+        gimme -2 ** 2;
+        gimme 2 ** 3 ** 2;
+        gimme 2 ** -1;
+        gimme 4 ** 0.5;
+        gimme 7 ~/ 2;
+        gimme -7 ~/ 2;
+        gimme 7 ~/ -2;
+        gimme 7.5 ~/ 2;",
+        "1024\n-4\n512\n0.5\n2\n3\n-4\n-4\n3",
+    );
+
+    test_code(
+        "gimme 12 & 10;
+        gimme 12 | 10;
+        gimme 12 ^ 10;
+        gimme 1 << 4;
+        gimme -16 >> 2;
+        gimme 0xF0 >> 4 & 0b11;
+        gimme 4.0 | 1;
+        gimme 1 | 2 == 3;",
+        "8\n14\n6\n16\n-4\n3\n5\nNah, yeah!",
+    );
+
+    // `>>` can still close two blocks at once
+    test_code(
+        "ya reckon 1 < 2 ? < ya reckon 2 < 3 ? < gimme 64 >> 3; >>",
+        "8",
+    );
+
+    test_code(
+        "give it a burl <
+            gimme 1.5 & 1;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme 2 ** 64;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme 1 << 63;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme 1 ~/ 0;
+        > strewth err <
+            gimme err[\"message\"];
+        >",
+        "OI MATE! & ONLY WORKS ON WHOLE NUMBERS, NOT 1.5
+STONE THE CROWS! 2 ** 64 IS TOO BIG FOR AN INTEGER
+STONE THE CROWS! 1 << 63 IS TOO BIG FOR AN INTEGER
+YA CAN'T DIVIDE BY ZERO, YA DRONGO!",
+    );
+
+    // Shifting by a count that doesn't fit isn't an overflow
+    test_code(
+        "gimme -1 >> 63;
+        gimme 1 << 62;
+        give it a burl <
+            gimme 1 >> 64;
+        > strewth err <
+            gimme err[\"message\"];
+        >
+        give it a burl <
+            gimme 1 << -1;
+        > strewth err <
+            gimme err[\"message\"];
+        >",
+        "-1
+4611686018427387904
+OI MATE! YA CAN'T SHIFT BY 64, IT'S GOTTA BE FROM 0 TO 63
+OI MATE! YA CAN'T SHIFT BY -1, IT'S GOTTA BE FROM 0 TO 63",
+    );
+}
+
+#[test]
+fn test_compound_assignment() {
+    test_code(
+        "i reckon x = 10;
+        x += 5;
+        x -= 3;
+        x *= 2;
+        gimme x;
+        x /= 8;
+        gimme x;
+        x %= 2;
+        gimme x;
+        i reckon s = \"g'day\";
+        s += \" mate\";
+        gimme s;
+        gimme x += 41;",
+        "24\n3\n1\ng'day mate\n42",
+    );
+
+    // The index is only worked out once
+    test_code(
+        "i reckon xs = [1, 2, 3];
+        i reckon i = 0;
+        xs[GOOD ON YA i] *= 10;
+        gimme xs;
+        gimme i;
+        i reckon m = {\"beers\": 1};
+        m[\"beers\"] += 5;
+        gimme m;",
+        "[1, 20, 3]\n1\n{\"beers\": 6}",
+    );
+
+    // Plain index assignment works left to right too: target, index, value
+    test_code(
+        "i reckon xs = [0, 0, 0];
+        i reckon i = 0;
+        xs[GOOD ON YA i] = i;
+        gimme xs;",
+        "[0, 1, 0]",
+    );
+
+    test("I FULLY RECKON x = 5; x += 1;", "", FallibleKind::Resolver);
+    test("I FULLY RECKON x = 5; x %= 2;", "", FallibleKind::Resolver);
+}
+
+#[test]
+fn test_lists() {
+    test_code(
+        "
+        I RECKON xs = [1, \"two\", NAH, YEAH!];
+        GIMME xs;
+        GIMME xs[1];
+        xs[0] = 420;
+        GIMME xs[0];
+        GIMME [];
+        ",
+        "[1, \"two\", Nah, yeah!]\ntwo\n420\n[]",
+    );
+
+    // Lists are shared, not copied
+    test_code(
+        "
+        I RECKON xs = [1, 2];
+        I RECKON ys = xs;
+        ys[0] = 69;
+        GIMME xs;
+        ",
+        "[69, 2]",
+    );
+
+    test_code("GIMME [1, 2] + [3];", "[1, 2, 3]");
+    test_code("GIMME [[1], 2][0][0];", "1");
+    test_code("GIMME \"strewth\"[2];", "r");
+
+    test_code("GIMME [1, [2, \"3\"]] == [1, [2, \"3\"]];", "Nah, yeah!");
+    test_code("GIMME [1, 2] == [1, 2, 3];", "Yeah, nah!");
+    test_code("GIMME [1, 2] != [2, 1];", "Nah, yeah!");
+
+    test_code(
+        "
+        IMPOHT ME FUNC HowLongIsAPieceOfString;
+        GIMME HowLongIsAPieceOfString([1, 2, 3]);
+        GIMME HowLongIsAPieceOfString(\"g'day\");
+        ",
+        "3\n5",
+    );
+
+    // A list inside itself is shown as `[...]` rather than going round forever
+    test_code(
+        "
+        I RECKON xs = [1];
+        xs[0] = xs;
+        GIMME xs;
+        I RECKON ys = [1];
+        ys[0] = ys;
+        GIMME xs == ys;
+        GIMME xs == [xs];
+        GIMME [xs, 2] == [ys, 3];
+        ",
+        "[[...]]\nNah, yeah!\nNah, yeah!\nYeah, nah!",
+    );
+
+    test("GIMME [1, 2][2];", "", FallibleKind::Interpreter);
+    test("GIMME [1, 2][0.5];", "", FallibleKind::Interpreter);
+    test("GIMME 5[0];", "", FallibleKind::Interpreter);
+}
+
+#[test]
+fn test_maps() {
+    test_code(
+        "
+        I RECKON esky = {\"VB\": 6, \"Coopers\": 12};
+        GIMME esky;
+        GIMME esky[\"VB\"];
+        esky[\"XXXX\"] = 24;
+        esky[\"VB\"] = 5;
+        GIMME esky;
+        GIMME esky[\"Fosters\"];
+        GIMME {};
+        ",
+        "{\"Coopers\": 12, \"VB\": 6}\n6\n{\"Coopers\": 12, \"VB\": 5, \"XXXX\": 24}\nbugger all\n{}",
+    );
+
+    test_code("GIMME {1: \"one\", NAH, YEAH!: 2}[1];", "one");
+    test_code(
+        "GIMME {\"a\": [1, {\"b\": 2}]} == {\"a\": [1, {\"b\": 2}]};",
+        "Nah, yeah!",
+    );
+    test_code("GIMME {\"a\": 1} == {\"a\": 2};", "Yeah, nah!");
+    test_code("GIMME {\"a\": 1} == {\"b\": 1};", "Yeah, nah!");
+
+    test_code(
+        "
+        IMPOHT ME FUNC GotAny;
+        IMPOHT ME FUNC BinIt;
+        IMPOHT ME FUNC GimmeKeys;
+        IMPOHT ME FUNC HowLongIsAPieceOfString;
+
+        I RECKON esky = {\"VB\": 6, \"Coopers\": 12};
+        GIMME GimmeKeys(esky);
+        GIMME GotAny(esky, \"VB\");
+        GIMME BinIt(esky, \"VB\");
+        GIMME GotAny(esky, \"VB\");
+        GIMME BinIt(esky, \"VB\");
+        GIMME HowLongIsAPieceOfString(esky);
+        ",
+        "[\"Coopers\", \"VB\"]\nNah, yeah!\n6\nYeah, nah!\nbugger all\n1",
+    );
+
+    // So is a map inside itself
+    test_code(
+        "
+        I RECKON m = {\"a\": 1};
+        m[\"self\"] = m;
+        GIMME m;
+        I RECKON n = {\"a\": 1};
+        n[\"self\"] = n;
+        GIMME m == n;
+        n[\"a\"] = 2;
+        GIMME m == n;
+        ",
+        "{\"a\": 1, \"self\": {...}}\nNah, yeah!\nYeah, nah!",
+    );
+
+    test("GIMME {[1]: 2};", "", FallibleKind::Interpreter);
+    test(
+        "I RECKON m = {}; m[BUGGER ALL] = 1;",
+        "",
+        FallibleKind::Interpreter,
+    );
+}
+
+#[test]
+fn test_for_each_loop() {
+    test_code(
+        "
+    I RECKON x IS A WALKABOUT THROUGH \"oi!\" <
+        GIMME x;
+    >
+    ",
+        "o\ni\n!",
+    );
+
+    test_code(
+        "
+    I RECKON x IS A WALKABOUT THROUGH [1, \"two\", [3]] <
+        GIMME x;
+    >
+    ",
+        "1\ntwo\n[3]",
+    );
+
+    test_code(
+        "
+    I RECKON x IS A WALKABOUT THROUGH {\"b\": 2, \"a\": 1} <
+        GIMME x;
+    >
+    ",
+        "a\nb",
+    );
+
+    test_code(
+        "
+    I RECKON xs = [1, 2, 3, 4];
+    I RECKON x IS A WALKABOUT THROUGH xs <
+        YA RECKON x == 3 ? MATE FUCK THIS;
+        GIMME x;
+    >
+    ",
+        "1\n2",
+    );
+
+    test_code(
+        "
+    I RECKON x IS A WALKABOUT THROUGH \"\" <
+        GIMME x;
+    >
+    ",
+        "",
+    );
+
+    test(
+        "
+    I RECKON x IS A WALKABOUT THROUGH 420 <
+        GIMME x;
+    >
+    ",
+        "",
+        FallibleKind::Interpreter,
+    );
+}
@@ -1,14 +1,14 @@
 use aussie_plus_plus::{
     lexer::{self, source::Source},
-    token::{Kind, Token},
+    token::{Kind, StringPart, Token},
 };
 
 fn test_lexing_with_src<T: Source>(mut expected_tokens: Vec<Token>, expected_error: bool, iter: T) {
     expected_tokens.insert(0, Token::new(Kind::GdayMate, 1));
     let mut lexer = lexer::Lexer::new(iter);
-    let (tokens, had_error) = lexer.lex();
+    let (tokens, diagnostics) = lexer.lex();
 
-    assert_eq!(had_error, expected_error);
+    assert_eq!(!diagnostics.is_empty(), expected_error);
     {
         for (i, token) in tokens.iter().enumerate() {
             if &expected_tokens[i] != token {
@@ -44,7 +44,7 @@ pub fn test_lex_fully_reckon() {
             Token::new(Kind::IFullyReckon, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::Assign, 1),
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Semicolon, 1),
             Token::new(Kind::EOF, 1),
         ],
@@ -65,9 +65,21 @@ pub fn test_lex_block_comment() {
     test_lexing(
         "5 + OI MATE! inside an expression GOT IT? 12;",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Plus, 1),
-            Token::new(Kind::Number(12f64), 1),
+            Token::new(Kind::Integer(12), 1),
+            Token::new(Kind::Semicolon, 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    test_lexing(
+        "outer: oi;",
+        vec![
+            Token::new(Kind::Ident("outer".into()), 1),
+            Token::new(Kind::Colon, 1),
+            Token::new(Kind::Ident("oi".into()), 1),
             Token::new(Kind::Semicolon, 1),
             Token::new(Kind::EOF, 1),
         ],
@@ -118,6 +130,161 @@ pub fn test_lex_bool() {
     );
 }
 
+#[test]
+pub fn test_lex_interpolation() {
+    test_lexing(
+        "\"g'day ${name}!\nthat's ${n +
+        1} beers\"",
+        vec![
+            Token::new(
+                Kind::InterpolatedString(vec![
+                    StringPart::Literal("g'day ".into()),
+                    StringPart::Expr(vec![
+                        Token::new(Kind::Ident("name".into()), 1),
+                        Token::new(Kind::EOF, 1),
+                    ]),
+                    StringPart::Literal("!\nthat's ".into()),
+                    StringPart::Expr(vec![
+                        Token::new(Kind::Ident("n".into()), 2),
+                        Token::new(Kind::Plus, 2),
+                        Token::new(Kind::Integer(1), 3),
+                        Token::new(Kind::EOF, 3),
+                    ]),
+                    StringPart::Literal(" beers".into()),
+                ]),
+                3,
+            ),
+            Token::new(Kind::EOF, 3),
+        ],
+        false,
+    );
+
+    test_lexing(
+        "\"${}\"",
+        vec![Token::new(Kind::Error, 1), Token::new(Kind::EOF, 1)],
+        true,
+    );
+}
+
+#[test]
+pub fn test_lex_numbers() {
+    test_lexing(
+        "0xFF 0Xdead_BEEF 0b1010 0B1111_0000 1_000_000 3.141_59 1e3 2.5E-3 6e+2 7. 9223372036854775807",
+        vec![
+            Token::new(Kind::Integer(255), 1),
+            Token::new(Kind::Integer(3735928559), 1),
+            Token::new(Kind::Integer(10), 1),
+            Token::new(Kind::Integer(240), 1),
+            Token::new(Kind::Integer(1000000), 1),
+            Token::new(Kind::Number(3.14159f64), 1),
+            Token::new(Kind::Number(1000f64), 1),
+            Token::new(Kind::Number(0.0025f64), 1),
+            Token::new(Kind::Number(600f64), 1),
+            Token::new(Kind::Number(7f64), 1),
+            Token::new(Kind::Integer(i64::MAX), 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    for bad in [
+        "1.2.3",
+        "1e",
+        "2e+",
+        "0x",
+        "0b",
+        "0b102",
+        "0xFG",
+        "0x1.5",
+        "1__0",
+        "1_",
+        "0x_1",
+        "1._5",
+        "1e999",
+        "9223372036854775808",
+        "0x8000_0000_0000_0000",
+    ] {
+        test_lexing(
+            bad,
+            vec![Token::new(Kind::Error, 1), Token::new(Kind::EOF, 1)],
+            true,
+        );
+    }
+}
+
+#[test]
+pub fn test_lex_string_escapes() {
+    test_lexing(
+        r#""tab\there\n\"quoted\" \\ \${not} \u{1F998}\0""#,
+        vec![
+            Token::new(
+                Kind::String("tab\there\n\"quoted\" \\ ${not} \u{1F998}\0".into()),
+                1,
+            ),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    // Escaped quotes inside an interpolated string don't close it
+    test_lexing(
+        r#""${"say \"g'day\""}""#,
+        vec![
+            Token::new(
+                Kind::InterpolatedString(vec![StringPart::Expr(vec![
+                    Token::new(Kind::String("say \"g'day\"".into()), 1),
+                    Token::new(Kind::EOF, 1),
+                ])]),
+                1,
+            ),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    // Bad escapes are reported and the rest of the string is skipped
+    test_lexing(
+        r#""bad \q escape" 1"#,
+        vec![
+            Token::new(Kind::Error, 1),
+            Token::new(Kind::Integer(1), 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        true,
+    );
+    test_lexing(
+        r#""\u{110000}" "\u{D800}" "\u{zz}" "\u1F998""#,
+        vec![
+            Token::new(Kind::Error, 1),
+            Token::new(Kind::Error, 1),
+            Token::new(Kind::Error, 1),
+            Token::new(Kind::Error, 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        true,
+    );
+}
+
+#[test]
+pub fn test_lex_raw_string() {
+    test_lexing(
+        r#""""<p class="${name}">
+\n</p>""" 1"#,
+        vec![
+            Token::new(Kind::String("<p class=\"${name}\">\n\\n</p>".into()), 2),
+            Token::new(Kind::Integer(1), 2),
+            Token::new(Kind::EOF, 2),
+        ],
+        false,
+    );
+
+    test_lexing(
+        r#""""never closed"#,
+        vec![Token::new(Kind::Error, 1), Token::new(Kind::EOF, 1)],
+        true,
+    );
+}
+
 #[test]
 pub fn test_lex_is() {
     test_lexing(
@@ -142,12 +309,12 @@ pub fn test_upside_down() {
             Token::new(Kind::IReckon, 2),
             Token::new(Kind::Ident("x".into()), 2),
             Token::new(Kind::Assign, 2),
-            Token::new(Kind::Number(5f64), 2),
+            Token::new(Kind::Integer(5), 2),
             Token::new(Kind::Semicolon, 2),
             Token::new(Kind::IReckon, 3),
             Token::new(Kind::Ident("y".into()), 3),
             Token::new(Kind::Assign, 3),
-            Token::new(Kind::Number(10f64), 3),
+            Token::new(Kind::Integer(10), 3),
             Token::new(Kind::Semicolon, 3),
             Token::new(Kind::Cheers, 4),
             Token::new(Kind::EOF, 5),
@@ -183,6 +350,10 @@ pub fn test_lex_keyword_casing() {
         Kind::False,
         Kind::True,
         Kind::BuggerAll,
+        Kind::ShellBeRight,
+        Kind::GiveItABurl,
+        Kind::Strewth,
+        Kind::ChuckAWobbly,
     ];
 
     fn test_random_case(kind: &Kind, iterations: usize) {
@@ -254,11 +425,11 @@ pub fn test_lex_keywords() {
             Token::new(Kind::IReckon, 2),
             Token::new(Kind::Ident("x".into()), 2),
             Token::new(Kind::Assign, 2),
-            Token::new(Kind::Number(5f64), 2),
+            Token::new(Kind::Integer(5), 2),
             Token::new(Kind::IReckon, 3),
             Token::new(Kind::Ident("y".into()), 3),
             Token::new(Kind::Assign, 3),
-            Token::new(Kind::Number(10f64), 3),
+            Token::new(Kind::Integer(10), 3),
             Token::new(Kind::Cheers, 4),
             Token::new(Kind::EOF, 5),
         ],
@@ -277,7 +448,7 @@ fn test_lex_ya_reckon() {
             Token::new(Kind::YaReckon, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::Equals, 1),
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::LeftBoomerang, 1),
             Token::new(Kind::Bail, 2),
             Token::new(Kind::True, 2),
@@ -302,24 +473,24 @@ fn test_lex_walkabout() {
             Token::new(Kind::IReckon, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::Assign, 1),
-            Token::new(Kind::Number(0f64), 1),
+            Token::new(Kind::Integer(0), 1),
             Token::new(Kind::Semicolon, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::LeftBoomerang, 1),
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Semicolon, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::Assign, 1),
             Token::new(Kind::Ident("x".into()), 1),
             Token::new(Kind::Plus, 1),
-            Token::new(Kind::Number(1f64), 1),
+            Token::new(Kind::Integer(1), 1),
             Token::new(Kind::RightParen, 1),
             Token::new(Kind::LeftBoomerang, 1),
             Token::new(Kind::Ident("x".into()), 2),
             Token::new(Kind::Assign, 2),
             Token::new(Kind::Ident("x".into()), 2),
             Token::new(Kind::Plus, 2),
-            Token::new(Kind::Number(1f64), 2),
+            Token::new(Kind::Integer(1), 2),
             Token::new(Kind::RightBoomerang, 3),
             Token::new(Kind::EOF, 3),
         ],
@@ -327,6 +498,24 @@ fn test_lex_walkabout() {
     );
 }
 
+#[test]
+fn test_lex_walkabout_through() {
+    test_lexing(
+        "I RECKON x IS A WALKABOUT THROUGH thing <",
+        vec![
+            Token::new(Kind::IReckon, 1),
+            Token::new(Kind::Ident("x".into()), 1),
+            Token::new(Kind::Isa, 1),
+            Token::new(Kind::Walkabout, 1),
+            Token::new(Kind::Through, 1),
+            Token::new(Kind::Ident("thing".into()), 1),
+            Token::new(Kind::LeftBoomerang, 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+}
+
 #[test]
 pub fn test_lex_incr_decr_ops() {
     test_lexing(
@@ -350,9 +539,9 @@ pub fn test_operators() {
     test_lexing(
         "5 < 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::LeftBoomerang, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -361,9 +550,9 @@ pub fn test_operators() {
     test_lexing(
         "5 > 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::RightBoomerang, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -372,9 +561,9 @@ pub fn test_operators() {
     test_lexing(
         "5 <= 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::LTE, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -383,9 +572,9 @@ pub fn test_operators() {
     test_lexing(
         "5 >= 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::GTE, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -394,9 +583,9 @@ pub fn test_operators() {
     test_lexing(
         "5 + 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Plus, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -405,9 +594,9 @@ pub fn test_operators() {
     test_lexing(
         "5 - 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Minus, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -416,9 +605,9 @@ pub fn test_operators() {
     test_lexing(
         "5 * 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Asterisk, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -427,9 +616,9 @@ pub fn test_operators() {
     test_lexing(
         "5 / 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Slash, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -438,9 +627,9 @@ pub fn test_operators() {
     test_lexing(
         "5 == 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Equals, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -449,9 +638,9 @@ pub fn test_operators() {
     test_lexing(
         "5 != 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::BangEqual, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -460,9 +649,9 @@ pub fn test_operators() {
     test_lexing(
         "5 && 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::And, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -471,9 +660,9 @@ pub fn test_operators() {
     test_lexing(
         "5 || 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Or, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -482,9 +671,67 @@ pub fn test_operators() {
     test_lexing(
         "5 % 10",
         vec![
-            Token::new(Kind::Number(5f64), 1),
+            Token::new(Kind::Integer(5), 1),
             Token::new(Kind::Modulo, 1),
-            Token::new(Kind::Number(10f64), 1),
+            Token::new(Kind::Integer(10), 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    test_lexing(
+        "2 ** 3 ~/ 4 & 5 | 6 ^ 7 << 8 >> 9 ~// comment",
+        vec![
+            Token::new(Kind::Integer(2), 1),
+            Token::new(Kind::Power, 1),
+            Token::new(Kind::Integer(3), 1),
+            Token::new(Kind::FloorDivide, 1),
+            Token::new(Kind::Integer(4), 1),
+            Token::new(Kind::BitAnd, 1),
+            Token::new(Kind::Integer(5), 1),
+            Token::new(Kind::BitOr, 1),
+            Token::new(Kind::Integer(6), 1),
+            Token::new(Kind::BitXor, 1),
+            Token::new(Kind::Integer(7), 1),
+            Token::new(Kind::ShiftLeft, 1),
+            Token::new(Kind::Integer(8), 1),
+            Token::new(Kind::ShiftRight, 1),
+            Token::new(Kind::Integer(9), 1),
+            Token::new(Kind::Tilde, 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    test_lexing(
+        "x += 1 -= 2 *= 3 /= 4 %= 5",
+        vec![
+            Token::new(Kind::Ident("x".into()), 1),
+            Token::new(Kind::PlusEqual, 1),
+            Token::new(Kind::Integer(1), 1),
+            Token::new(Kind::MinusEqual, 1),
+            Token::new(Kind::Integer(2), 1),
+            Token::new(Kind::AsteriskEqual, 1),
+            Token::new(Kind::Integer(3), 1),
+            Token::new(Kind::SlashEqual, 1),
+            Token::new(Kind::Integer(4), 1),
+            Token::new(Kind::ModuloEqual, 1),
+            Token::new(Kind::Integer(5), 1),
+            Token::new(Kind::EOF, 1),
+        ],
+        false,
+    );
+
+    test_lexing(
+        "{key: [1]}",
+        vec![
+            Token::new(Kind::LeftBrace, 1),
+            Token::new(Kind::Ident("key".into()), 1),
+            Token::new(Kind::Colon, 1),
+            Token::new(Kind::LeftBracket, 1),
+            Token::new(Kind::Integer(1), 1),
+            Token::new(Kind::RightBracket, 1),
+            Token::new(Kind::RightBrace, 1),
             Token::new(Kind::EOF, 1),
         ],
         false,
@@ -500,3 +747,102 @@ pub fn test_operators() {
         false,
     );
 }
+
+#[test]
+fn test_lex_spans() {
+    use aussie_plus_plus::span::{Position, Span};
+
+    let src = "G'day mate! gimme \"é\";\nx";
+    let mut lexer = lexer::Lexer::new(lexer::source::Regular::new(src.chars()));
+    let (tokens, diagnostics) = lexer.lex();
+    assert!(diagnostics.is_empty());
+
+    let spans: Vec<Span> = tokens.iter().map(|tok| tok.span()).collect();
+    let span = |start: (usize, usize, usize), end: (usize, usize, usize)| {
+        Span::new(
+            Position::new(start.0, start.1, start.2),
+            Position::new(end.0, end.1, end.2),
+        )
+    };
+    assert_eq!(
+        spans,
+        vec![
+            span((0, 1, 1), (11, 1, 12)),
+            span((12, 1, 13), (17, 1, 18)),
+            // `é` is two bytes but one column
+            span((18, 1, 19), (22, 1, 22)),
+            span((22, 1, 22), (23, 1, 23)),
+            span((24, 2, 1), (25, 2, 2)),
+            span((25, 2, 2), (25, 2, 2)),
+        ]
+    );
+}
+
+#[test]
+fn test_lex_reports_every_error() {
+    let src = "G'day mate! gimme \"\\q and \\w\";\ngimme 1 @ 2;\ngimme 0x;";
+    let mut lexer = lexer::Lexer::new(lexer::source::Regular::new(src.chars()));
+    let (tokens, diagnostics) = lexer.lex();
+
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            let span = d.span.unwrap();
+            (d.code, span.start.line, span.start.col, span.end.col)
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            ("L015", 1, 20, 22),
+            ("L015", 1, 27, 29),
+            ("L006", 2, 9, 10),
+            ("L010", 3, 7, 9)
+        ]
+    );
+
+    // Each mistake leaves an error token behind, and nothing else
+    assert_eq!(
+        tokens.iter().map(Token::kind).collect::<Vec<_>>(),
+        [
+            Kind::GdayMate,
+            Kind::Gimme,
+            Kind::Error,
+            Kind::Semicolon,
+            Kind::Gimme,
+            Kind::Integer(1),
+            Kind::Error,
+            Kind::Integer(2),
+            Kind::Semicolon,
+            Kind::Gimme,
+            Kind::Error,
+            Kind::Semicolon,
+            Kind::EOF,
+        ]
+    );
+
+    // A boolean missing its `!` is one mistake, not one for every token after it
+    let src = "G'day mate! gimme YEAH;\ngimme 1 + 2;";
+    let mut lexer = lexer::Lexer::new(lexer::source::Regular::new(src.chars()));
+    let (tokens, diagnostics) = lexer.lex();
+
+    assert_eq!(
+        diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(),
+        ["L002"]
+    );
+    assert_eq!(
+        tokens.iter().map(Token::kind).collect::<Vec<_>>(),
+        [
+            Kind::GdayMate,
+            Kind::Gimme,
+            Kind::Error,
+            Kind::Semicolon,
+            Kind::Gimme,
+            Kind::Integer(1),
+            Kind::Plus,
+            Kind::Integer(2),
+            Kind::Semicolon,
+            Kind::EOF,
+        ]
+    );
+}
@@ -1,7 +1,7 @@
 use aussie_plus_plus::{
     ast::{
-        BinaryOp, Expr, ExprNode, ForLoop, Ident, If, Match, MatchBranch, Pattern, RangeBound,
-        Stmt, UnaryOp, Var, VarDecl,
+        BinaryOp, Expr, ExprNode, ForEachLoop, ForLoop, Ident, If, Match, MatchBranch, Pattern,
+        RangeBound, Stmt, UnaryOp, Var, VarDecl,
     },
     lexer::{lexer, source},
     parser::parser,
@@ -51,7 +51,7 @@ fn test_parse_for_loop() {
             mate fuck this;
             >",
         |stmts| {
-            let inner = Stmt::Break(Token::new(Kind::MateFuckThis, 2));
+            let inner = Stmt::Break(Token::new(Kind::MateFuckThis, 2), None);
             let body = vec![Stmt::Block(vec![inner])];
             let range = (
                 RangeBound::Exclusive(ExprNode::new(Expr::Literal(0.into()), 1)),
@@ -69,6 +69,105 @@ fn test_parse_for_loop() {
     );
 }
 
+#[test]
+fn test_parse_for_loop_step() {
+    test_parse(
+        "i reckon x is a walkabout from [0 to 10] hoppin 2 <
+            gimme x;
+            >",
+        |stmts| {
+            let inner = Stmt::Print(ExprNode::new(Expr::Var(("x", 2, usize::MAX).into()), 2));
+            let body = vec![Stmt::Block(vec![inner])];
+            let range = (
+                RangeBound::Inclusive(ExprNode::new(Expr::Literal(0.into()), 1)),
+                RangeBound::Inclusive(ExprNode::new(Expr::Literal(10.into()), 1)),
+            );
+            assert_eq!(
+                stmts[0],
+                Stmt::For(Box::new(ForLoop::new_with_step(
+                    Var::new(("x", 1).into(), usize::MAX),
+                    range,
+                    Some(ExprNode::new(Expr::Literal(2.into()), 1)),
+                    body
+                )))
+            )
+        },
+    );
+}
+
+#[test]
+fn test_parse_for_each_loop() {
+    test_parse(
+        "i reckon x is a walkabout through xs <
+            gimme x;
+            >",
+        |stmts| {
+            let inner = Stmt::Print(ExprNode::new(Expr::Var(("x", 2, usize::MAX).into()), 2));
+            let body = vec![Stmt::Block(vec![inner])];
+            assert_eq!(
+                stmts[0],
+                Stmt::ForEach(Box::new(ForEachLoop::new(
+                    Var::new(("x", 1).into(), usize::MAX),
+                    ExprNode::new(Expr::Var(("xs", 1, usize::MAX).into()), 1),
+                    body
+                )))
+            )
+        },
+    );
+
+    // Without a name there's nothing to put each item in
+    let source = "G'DAY MATE!
+        i reckon i'll have a walkabout through [1] <
+            gimme 1;
+        >
+        gimme 2;";
+    let mut lex = lexer::Lexer::new(source::Regular::new(source.chars()));
+    let (tokens, _) = lex.lex();
+    let (stmts, diagnostics) = parser::Parser::new(tokens).parse_partial();
+
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.line())).collect();
+    assert_eq!(errors, [("P014", Some(2))]);
+    assert_eq!(
+        diagnostics[0].message,
+        "OI MATE! EXPECTED A NAME BEFORE THROUGH"
+    );
+    assert_eq!(stmts.len(), 1);
+
+    // `through` is reserved like the other loop keywords
+    let source = "G'DAY MATE! i reckon through = 7;";
+    let mut lex = lexer::Lexer::new(source::Regular::new(source.chars()));
+    let (tokens, _) = lex.lex();
+    let (_, diagnostics) = parser::Parser::new(tokens).parse_partial();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("'through'"));
+}
+
+#[test]
+fn test_parse_labelled_loop() {
+    test_parse(
+        "outer: i reckon x is a walkabout through xs <
+            mate fuck this outer;
+            >",
+        |stmts| {
+            let label: Ident = ("outer", 1).into();
+            let inner = Stmt::Break(Token::new(Kind::MateFuckThis, 2), Some(("outer", 2).into()));
+            let body = vec![Stmt::Block(vec![inner])];
+            let for_each = ForEachLoop::new(
+                Var::new(("x", 1).into(), usize::MAX),
+                ExprNode::new(Expr::Var(("xs", 1, usize::MAX).into()), 1),
+                body,
+            );
+            assert_eq!(
+                stmts[0],
+                Stmt::ForEach(Box::new(ForEachLoop {
+                    label: Some(label),
+                    ..for_each
+                }))
+            )
+        },
+    );
+}
+
 #[test]
 fn test_parse_assign() {
     test_parse(
@@ -147,7 +246,40 @@ fn test_parse_match() {
                     3,
                 ),
             ];
-            assert_eq!(stmts[0], Stmt::Match(Match::new(cond, branches, None)));
+            assert_eq!(
+                stmts[0],
+                Stmt::Match(Box::new(Match::new(cond, branches, None)))
+            );
+        },
+    );
+}
+
+#[test]
+fn test_parse_match_patterns() {
+    test_parse(
+        "ya reckon x is a <
+                    1, 2 ~ 1;
+                    [0 to 10) ya reckon Nah, yeah! ~ 2;
+                >",
+        |stmts| {
+            let cond = ExprNode::new(Expr::Var(("x", 1, usize::MAX).into()), 1);
+            let branches = vec![
+                MatchBranch::new(
+                    Pattern::Alternatives(vec![Pattern::Integer(1), Pattern::Integer(2)]),
+                    vec![Stmt::Expr(ExprNode::new(Expr::Literal(1.into()), 2))],
+                    2,
+                ),
+                MatchBranch::new_with_guard(
+                    Pattern::Range(RangeBound::Inclusive(0.0), RangeBound::Exclusive(10.0)),
+                    Some(ExprNode::new(Expr::Literal(true.into()), 3)),
+                    vec![Stmt::Expr(ExprNode::new(Expr::Literal(2.into()), 3))],
+                    3,
+                ),
+            ];
+            assert_eq!(
+                stmts[0],
+                Stmt::Match(Box::new(Match::new(cond, branches, None)))
+            );
         },
     );
 }
@@ -223,7 +355,10 @@ fn test_parse_block() {
                     3,
                 ),
             ];
-            assert_eq!(stmts[0], Stmt::Match(Match::new(cond, branches, None)));
+            assert_eq!(
+                stmts[0],
+                Stmt::Match(Box::new(Match::new(cond, branches, None)))
+            );
         },
     );
 }
@@ -608,3 +743,213 @@ fn test_parse_binary_op() {
         );
     });
 }
+
+#[test]
+fn test_parse_operator_precedence() {
+    let lit = |n: i64| Box::new(ExprNode::new(Expr::Literal(n.into()), 1));
+    let binary = |l, op, r| Box::new(ExprNode::new(Expr::Binary(l, op, r), 1));
+
+    // Loosest to tightest: | ^ & shifts + *
+    test_parse("1 | 2 ^ 3 & 4 << 5 + 6 * 7 == 8;", |stmts| {
+        let product = binary(lit(6), BinaryOp::Multiply, lit(7));
+        let sum = binary(lit(5), BinaryOp::Plus, product);
+        let shift = binary(lit(4), BinaryOp::ShiftLeft, sum);
+        let and = binary(lit(3), BinaryOp::BitAnd, shift);
+        let xor = binary(lit(2), BinaryOp::BitXor, and);
+        let or = binary(lit(1), BinaryOp::BitOr, xor);
+        assert_eq!(stmts[0], Stmt::Expr(*binary(or, BinaryOp::Equal, lit(8))));
+    });
+
+    // Powers bind tighter than a unary minus and group to the right
+    test_parse("-2 ** 3 ** 2 ~/ 4;", |stmts| {
+        let power = binary(
+            lit(2),
+            BinaryOp::Power,
+            binary(lit(3), BinaryOp::Power, lit(2)),
+        );
+        let neg = Box::new(ExprNode::new(Expr::Unary(UnaryOp::Minus, power), 1));
+        assert_eq!(
+            stmts[0],
+            Stmt::Expr(*binary(neg, BinaryOp::FloorDivide, lit(4)))
+        );
+    });
+
+    // `>>` closing two blocks at once isn't a shift
+    test_parse(
+        "ya reckon 1 ? < ya reckon 2 ? < gimme 3 >> 1; >>",
+        |stmts| {
+            let shift = binary(lit(3), BinaryOp::ShiftRight, lit(1));
+            let inner = Stmt::If(If::new(
+                *lit(2),
+                Box::new(Stmt::Block(vec![Stmt::Print(*shift)])),
+                None,
+            ));
+            assert_eq!(
+                stmts[0],
+                Stmt::If(If::new(*lit(1), Box::new(Stmt::Block(vec![inner])), None))
+            );
+        },
+    );
+}
+
+#[test]
+fn test_parse_compound_assignment() {
+    let x = || ExprNode::new(Expr::Var(("x", 1, usize::MAX).into()), 1);
+    let lit = |n: i64| Box::new(ExprNode::new(Expr::Literal(n.into()), 1));
+
+    test_parse("x *= 2 + 1;", |stmts| {
+        let sum = ExprNode::new(Expr::Binary(lit(2), BinaryOp::Plus, lit(1)), 1);
+        let product = ExprNode::new(
+            Expr::Binary(Box::new(x()), BinaryOp::Multiply, Box::new(sum)),
+            1,
+        );
+        assert_eq!(
+            stmts[0],
+            Stmt::Expr(ExprNode::new(
+                Expr::Assign(("x", 1, usize::MAX).into(), Box::new(product)),
+                1
+            ))
+        );
+    });
+
+    test_parse("x[0] -= 1;", |stmts| {
+        assert_eq!(
+            stmts[0],
+            Stmt::Expr(ExprNode::new(
+                Expr::IndexUpdate(Box::new(x()), lit(0), BinaryOp::Minus, lit(1)),
+                1
+            ))
+        );
+    });
+}
+
+#[test]
+fn test_parse_list() {
+    test_parse("[1, 2][0] = 3;", |stmts| {
+        let list = ExprNode::new(
+            Expr::List(vec![
+                ExprNode::new(Expr::Literal(1.into()), 1),
+                ExprNode::new(Expr::Literal(2.into()), 1),
+            ]),
+            1,
+        );
+        assert_eq!(
+            stmts[0],
+            Stmt::Expr(ExprNode::new(
+                Expr::IndexAssign(
+                    Box::new(list),
+                    Box::new(ExprNode::new(Expr::Literal(0.into()), 1)),
+                    Box::new(ExprNode::new(Expr::Literal(3.into()), 1)),
+                ),
+                1
+            ))
+        );
+    });
+
+    test_parse("xs[0];", |stmts| {
+        assert_eq!(
+            stmts[0],
+            Stmt::Expr(ExprNode::new(
+                Expr::Index(
+                    Box::new(ExprNode::new(Expr::Var(("xs", 1, usize::MAX).into()), 1)),
+                    Box::new(ExprNode::new(Expr::Literal(0.into()), 1)),
+                ),
+                1
+            ))
+        );
+    });
+}
+
+#[test]
+fn test_parse_spans() {
+    test_parse("gimme 1 + foo(2);", |stmts| match &stmts[0] {
+        Stmt::Print(node) => {
+            let span = node.span();
+            assert_eq!((span.start.col, span.end.col), (19, 29));
+
+            match node.expr() {
+                Expr::Binary(_, _, rhs) => {
+                    let span = rhs.span();
+                    assert_eq!((span.start.offset, span.end.offset), (22, 28));
+                }
+                expr => panic!("expected a binary expression but got {:?}", expr),
+            }
+        }
+        stmt => panic!("expected a print but got {:?}", stmt),
+    });
+}
+
+#[test]
+fn test_parse_recovery() {
+    let source = "G'DAY MATE!
+        i reckon x = 1
+        gimme x;
+        <
+            gimme (1 + ;
+            gimme 2;
+        >
+        ya reckon x is a <
+            1 ~ gimme 1 +;
+            2 ~ gimme 2;
+        >
+        gimme 3;";
+    let mut lex = lexer::Lexer::new(source::Regular::new(source.chars()));
+    let (tokens, _) = lex.lex();
+    let (stmts, diagnostics) = parser::Parser::new(tokens).parse_partial();
+
+    // One error for each mistake, not everything that follows them
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.code, d.line()))
+            .collect::<Vec<_>>(),
+        [("P002", Some(3)), ("P009", Some(5)), ("P009", Some(9))]
+    );
+
+    // The statements around the mistakes are still there, including the
+    // `gimme` the missing `;` ran into
+    assert_eq!(stmts.len(), 4);
+    match &stmts[1] {
+        Stmt::Block(block) => assert_eq!(block.len(), 1),
+        stmt => panic!("expected a block but got {:?}", stmt),
+    }
+    match &stmts[2] {
+        Stmt::Match(match_) => assert_eq!(match_.branches.len(), 1),
+        stmt => panic!("expected a match but got {:?}", stmt),
+    }
+
+    // A `;` missing before a block's closing `>` doesn't take the `>` with it
+    let source = "G'DAY MATE!
+        <
+            gimme 1
+        >
+        the hard yakka for f is (n) <
+            bail n
+        >
+        gimme \"after\";";
+    let mut lex = lexer::Lexer::new(source::Regular::new(source.chars()));
+    let (tokens, _) = lex.lex();
+    let (stmts, diagnostics) = parser::Parser::new(tokens).parse_partial();
+
+    let errors: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.code, d.span.map(|span| (span.start.line, span.start.col))))
+        .collect();
+    assert_eq!(errors, [("P013", Some((4, 9))), ("P013", Some((7, 9)))]);
+    assert_eq!(stmts.len(), 3);
+
+    // A walkabout that isn't from, through or until something is skipped
+    // as a whole
+    let source = "G'DAY MATE!
+        i reckon x is a walkabout over [1 to 2] <
+            gimme x;
+        >
+        gimme \"after\";";
+    let mut lex = lexer::Lexer::new(source::Regular::new(source.chars()));
+    let (tokens, _) = lex.lex();
+    let (stmts, diagnostics) = parser::Parser::new(tokens).parse_partial();
+
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.line())).collect();
+    assert_eq!(errors, [("P003", Some(2))]);
+    assert_eq!(stmts.len(), 1);
+}